@@ -4,12 +4,18 @@ use serde_json::json;
 /// Handles /anything endpoint
 /// Returns anything (echoes request data)
 pub fn anything_handler(req: &Request) -> Response {
+    let body = crate::body::parse_body(req);
+    
     let response_data = json!({
-        "args": crate::parse_query(&req.path.split('?').nth(1).unwrap_or("")),
+        "args": req.args(),
+        "data": body.data,
+        "files": body.files,
+        "form": body.form,
         "headers": req.headers,
+        "json": body.json,
         "method": req.method,
-        "origin": crate::get_client_ip(&req.headers).unwrap_or_else(|| "127.0.0.1".to_string()),
-        "url": format!("https://httpbin.org{}", req.path)
+        "origin": req.origin(),
+        "url": req.url()
     });
     
     Response::new(200).with_json(&response_data)
@@ -24,16 +30,10 @@ pub fn anything_path_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_anything_handler() {
-        let req = Request {
-            method: "POST".to_string(),
-            path: "/anything?foo=bar".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("POST", "/anything?foo=bar");
         
         let response = anything_handler(&req);
         assert_eq!(response.status, 200);
@@ -41,12 +41,7 @@ mod tests {
     
     #[test]
     fn test_anything_path_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/anything/some/path".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/anything/some/path");
         
         let response = anything_path_handler(&req);
         assert_eq!(response.status, 200);
@@ -4,19 +4,12 @@ use serde_json::json;
 /// Handles /basic-auth/{user}/{passwd} endpoint
 /// Basic authentication challenge
 pub fn basic_auth_handler(req: &Request) -> Response {
-    let params = crate::extract_params(&req.path, r"/(?:h[123]/)?basic-auth/([^/]+)/([^/?]+)")
-        .unwrap_or_default();
-    
-    if params.len() < 2 {
+    let (Some(expected_user), Some(expected_pass)) = (req.param("user"), req.param("passwd")) else {
         return Response::new(400).with_text("Invalid parameters");
-    }
-    
-    let expected_user = &params[0];
-    let expected_pass = &params[1];
+    };
     
     // Check for Authorization header
-    if let Some(auth_header) = req.headers.get("Authorization")
-        .or_else(|| req.headers.get("authorization")) {
+    if let Some(auth_header) = req.headers.get("Authorization") {
         
         if let Some(credentials) = auth_header.strip_prefix("Basic ") {
             // Decode base64 credentials
@@ -41,7 +34,7 @@ pub fn basic_auth_handler(req: &Request) -> Response {
     let mut response = Response::new(401);
     response.headers.insert(
         "WWW-Authenticate".to_string(),
-        "Basic realm=\"Fake Realm\"".to_string()
+        format!("Basic realm=\"{}\"", req.settings.realm)
     );
     response
 }
@@ -49,19 +42,12 @@ pub fn basic_auth_handler(req: &Request) -> Response {
 /// Handles /hidden-basic-auth/{user}/{passwd} endpoint
 /// Hidden basic auth (returns 404 on failure instead of 401)
 pub fn hidden_basic_auth_handler(req: &Request) -> Response {
-    let params = crate::extract_params(&req.path, r"/(?:h[123]/)?hidden-basic-auth/([^/]+)/([^/?]+)")
-        .unwrap_or_default();
-    
-    if params.len() < 2 {
+    let (Some(expected_user), Some(expected_pass)) = (req.param("user"), req.param("passwd")) else {
         return Response::new(400).with_text("Invalid parameters");
-    }
-    
-    let expected_user = &params[0];
-    let expected_pass = &params[1];
+    };
     
     // Check for Authorization header
-    if let Some(auth_header) = req.headers.get("Authorization")
-        .or_else(|| req.headers.get("authorization")) {
+    if let Some(auth_header) = req.headers.get("Authorization") {
         
         if let Some(credentials) = auth_header.strip_prefix("Basic ") {
             if let Ok(decoded) = crate::decode_base64(credentials) {
@@ -88,20 +74,14 @@ pub fn hidden_basic_auth_handler(req: &Request) -> Response {
 /// Handles /digest-auth/{qop}/{user}/{passwd} endpoint
 /// Digest authentication
 pub fn digest_auth_handler(req: &Request) -> Response {
-    let params = crate::extract_params(&req.path, r"/(?:h[123]/)?digest-auth/([^/]+)/([^/]+)/([^/?]+)")
-        .unwrap_or_default();
-    
-    if params.len() < 3 {
+    let (Some(qop), Some(expected_user), Some(_expected_pass)) =
+        (req.param("qop"), req.param("user"), req.param("passwd"))
+    else {
         return Response::new(400).with_text("Invalid parameters");
-    }
-
-    let qop = &params[0];
-    let expected_user = &params[1];
-    let _expected_pass = &params[2];
+    };
     
     // Check for Authorization header
-    if let Some(auth_header) = req.headers.get("Authorization")
-        .or_else(|| req.headers.get("authorization")) {
+    if let Some(auth_header) = req.headers.get("Authorization") {
         
         if auth_header.starts_with("Digest ") {
             // Parse digest parameters
@@ -126,8 +106,8 @@ pub fn digest_auth_handler(req: &Request) -> Response {
     let opaque = generate_nonce();
     
     let challenge = format!(
-        "Digest realm=\"Fake Realm\", nonce=\"{}\", opaque=\"{}\", qop=\"{}\"",
-        nonce, opaque, qop
+        "Digest realm=\"{}\", nonce=\"{}\", opaque=\"{}\", qop=\"{}\"",
+        req.settings.realm, nonce, opaque, qop
     );
     
     let mut response = Response::new(401);
@@ -138,23 +118,14 @@ pub fn digest_auth_handler(req: &Request) -> Response {
 /// Handles /digest-auth/{qop}/{user}/{passwd}/{algorithm} endpoint
 /// Digest authentication with algorithm specification
 pub fn digest_auth_algorithm_handler(req: &Request) -> Response {
-    let params = crate::extract_params(
-        &req.path,
-        r"/(?:h[123]/)?digest-auth/([^/]+)/([^/]+)/([^/]+)/([^/?]+)"
-    ).unwrap_or_default();
-    
-    if params.len() < 4 {
+    let (Some(qop), Some(expected_user), Some(_expected_pass), Some(algorithm)) =
+        (req.param("qop"), req.param("user"), req.param("passwd"), req.param("algorithm"))
+    else {
         return Response::new(400).with_text("Invalid parameters");
-    }
-    
-    let qop = &params[0];
-    let expected_user = &params[1];
-    let _expected_pass = &params[2];
-    let algorithm = &params[3];
+    };
     
     // Check for Authorization header
-    if let Some(auth_header) = req.headers.get("Authorization")
-        .or_else(|| req.headers.get("authorization")) {
+    if let Some(auth_header) = req.headers.get("Authorization") {
         
         if auth_header.starts_with("Digest ") {
             let auth_params = parse_digest_auth(auth_header);
@@ -176,8 +147,8 @@ pub fn digest_auth_algorithm_handler(req: &Request) -> Response {
     let opaque = generate_nonce();
     
     let challenge = format!(
-        "Digest realm=\"Fake Realm\", nonce=\"{}\", opaque=\"{}\", algorithm=\"{}\", qop=\"{}\"",
-        nonce, opaque, algorithm, qop
+        "Digest realm=\"{}\", nonce=\"{}\", opaque=\"{}\", algorithm=\"{}\", qop=\"{}\"",
+        req.settings.realm, nonce, opaque, algorithm, qop
     );
     
     let mut response = Response::new(401);
@@ -189,8 +160,7 @@ pub fn digest_auth_algorithm_handler(req: &Request) -> Response {
 /// Bearer token authentication
 pub fn bearer_handler(req: &Request) -> Response {
     // Check for Authorization header with Bearer token
-    if let Some(auth_header) = req.headers.get("Authorization")
-        .or_else(|| req.headers.get("authorization")) {
+    if let Some(auth_header) = req.headers.get("Authorization") {
         
         if let Some(token) = auth_header.strip_prefix("Bearer ") {
             // Token exists - authentication successful
@@ -238,20 +208,14 @@ fn generate_nonce() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_basic_auth_success() {
-        let mut headers = HashMap::new();
         // "user:pass" in base64 is "dXNlcjpwYXNz"
-        headers.insert("Authorization".to_string(), "Basic dXNlcjpwYXNz".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/basic-auth/user/pass".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/basic-auth/user/pass")
+            .with_param("user", "user")
+            .with_param("passwd", "pass")
+            .with_header("Authorization", "Basic dXNlcjpwYXNz");
         
         let response = basic_auth_handler(&req);
         assert_eq!(response.status, 200);
@@ -259,12 +223,9 @@ mod tests {
     
     #[test]
     fn test_basic_auth_failure() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/basic-auth/user/pass".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/basic-auth/user/pass")
+            .with_param("user", "user")
+            .with_param("passwd", "pass");
         
         let response = basic_auth_handler(&req);
         assert_eq!(response.status, 401);
@@ -272,13 +233,38 @@ mod tests {
     }
     
     #[test]
-    fn test_hidden_basic_auth_failure() {
+    fn test_configured_realm() {
         let req = Request {
-            method: "GET".to_string(),
-            path: "/hidden-basic-auth/user/pass".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
+            settings: std::sync::Arc::new(crate::EndpointSettings {
+                realm: "Mock Realm".to_string(),
+                ..Default::default()
+            }),
+            ..Request::new("GET", "/basic-auth/user/pass")
+                .with_param("user", "user")
+                .with_param("passwd", "pass")
         };
+
+        let response = basic_auth_handler(&req);
+        assert_eq!(response.headers.get("WWW-Authenticate"), Some("Basic realm=\"Mock Realm\""));
+    }
+
+    #[test]
+    fn test_basic_auth_encoded_credentials() {
+        // "a@b:p w" in base64 is "YUBiOnAgdw=="
+        let req = Request::new("GET", "/basic-auth/a%40b/p%20w")
+            .with_param("user", "a@b")
+            .with_param("passwd", "p w")
+            .with_header("Authorization", "Basic YUBiOnAgdw==");
+
+        let response = basic_auth_handler(&req);
+        assert_eq!(response.status, 200);
+    }
+
+    #[test]
+    fn test_hidden_basic_auth_failure() {
+        let req = Request::new("GET", "/hidden-basic-auth/user/pass")
+            .with_param("user", "user")
+            .with_param("passwd", "pass");
         
         let response = hidden_basic_auth_handler(&req);
         assert_eq!(response.status, 404);
@@ -286,15 +272,8 @@ mod tests {
     
     #[test]
     fn test_bearer_auth_success() {
-        let mut headers = HashMap::new();
-        headers.insert("Authorization".to_string(), "Bearer mytoken123".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/bearer".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/bearer")
+            .with_header("Authorization", "Bearer mytoken123");
         
         let response = bearer_handler(&req);
         assert_eq!(response.status, 200);
@@ -302,12 +281,7 @@ mod tests {
     
     #[test]
     fn test_bearer_auth_failure() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/bearer".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/bearer");
         
         let response = bearer_handler(&req);
         assert_eq!(response.status, 401);
@@ -315,12 +289,10 @@ mod tests {
     
     #[test]
     fn test_digest_auth_challenge() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/digest-auth/auth/user/pass".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/digest-auth/auth/user/pass")
+            .with_param("qop", "auth")
+            .with_param("user", "user")
+            .with_param("passwd", "pass");
         
         let response = digest_auth_handler(&req);
         assert_eq!(response.status, 401);
@@ -0,0 +1,141 @@
+use crate::{Params, Request};
+use serde_json::Value;
+
+/// Request body decoded into httpbin's `data`, `json`, `form` and `files` fields
+#[derive(Debug, Clone, Default)]
+pub struct ParsedBody {
+    pub data: String,
+    pub json: Value,
+    pub form: Params,
+    pub files: Params,
+}
+
+/// Decode the request body according to its Content-Type
+///
+/// - `application/x-www-form-urlencoded` fills `form` and leaves `data` empty
+/// - `multipart/form-data` fills `form` with plain fields and `files` with
+///   uploads, leaving `data` empty
+/// - JSON media types (`application/json`, `*+json`) keep the raw text in
+///   `data` and the parsed document in `json`
+/// - anything else is echoed in `data`, as a base64 data URL if it is not UTF-8
+pub fn parse_body(req: &Request) -> ParsedBody {
+    let mut parsed = ParsedBody::default();
+
+    if req.body.is_empty() {
+        return parsed;
+    }
+
+    let mime = media_type(req.content_type().unwrap_or(""));
+
+    if mime == "application/x-www-form-urlencoded" {
+        parsed.form = crate::parse_urlencoded(&String::from_utf8_lossy(&req.body));
+        return parsed;
+    }
+
+    if mime == "multipart/form-data" {
+        let parts = crate::multipart::boundary(req.content_type().unwrap_or(""))
+            .and_then(|boundary| crate::multipart::parse_multipart(&req.body, &boundary));
+
+        if let Some(parts) = parts {
+            for part in parts {
+                if part.is_file() {
+                    let mime = part.content_type.as_deref().unwrap_or("application/octet-stream");
+                    parsed.files.push(part.name, body_as_data(&part.data, mime));
+                } else {
+                    parsed.form.push(part.name, String::from_utf8_lossy(&part.data));
+                }
+            }
+            return parsed;
+        }
+    }
+
+    parsed.data = body_as_data(&req.body, "application/octet-stream");
+
+    if is_json(&mime) {
+        parsed.json = serde_json::from_slice(&req.body).unwrap_or(Value::Null);
+    }
+
+    parsed
+}
+
+/// Body as text, or a base64 data URL for binary content
+fn body_as_data(body: &[u8], mime: &str) -> String {
+    match std::str::from_utf8(body) {
+        Ok(text) => text.to_string(),
+        Err(_) => format!("data:{};base64,{}", mime, crate::encode_base64(body)),
+    }
+}
+
+/// Lowercased media type without parameters
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_json(mime: &str) -> bool {
+    mime == "application/json" || mime.ends_with("+json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_body() {
+        let req = Request::new("POST", "/post")
+            .with_header("content-type", "application/json; charset=utf-8")
+            .with_body(r#"{"a": 1}"#);
+
+        let parsed = parse_body(&req);
+        assert_eq!(parsed.data, r#"{"a": 1}"#);
+        assert_eq!(parsed.json["a"], 1);
+        assert!(parsed.form.is_empty());
+    }
+
+    #[test]
+    fn test_parse_form_body() {
+        let req = Request::new("POST", "/post")
+            .with_header("Content-Type", "application/x-www-form-urlencoded")
+            .with_body("custname=Ann+Lee&size=small");
+
+        let parsed = parse_body(&req);
+        assert_eq!(parsed.data, "");
+        assert_eq!(parsed.form.get("custname"), Some("Ann Lee"));
+        assert_eq!(parsed.json, Value::Null);
+    }
+
+    #[test]
+    fn test_parse_multipart_body() {
+        let req = Request::new("POST", "/post")
+            .with_header("Content-Type", "multipart/form-data; boundary=b1")
+            .with_body(
+                &b"--b1\r\n\
+Content-Disposition: form-data; name=\"note\"\r\n\r\nhi\r\n\
+--b1\r\n\
+Content-Disposition: form-data; name=\"doc\"; filename=\"a.txt\"\r\n\
+Content-Type: text/plain\r\n\r\nplain text\r\n\
+--b1\r\n\
+Content-Disposition: form-data; name=\"img\"; filename=\"a.png\"\r\n\
+Content-Type: image/png\r\n\r\n\x89PNG\r\n\
+--b1--\r\n"[..],
+            );
+
+        let parsed = parse_body(&req);
+        assert_eq!(parsed.data, "");
+        assert_eq!(parsed.form.get("note"), Some("hi"));
+        assert_eq!(parsed.files.get("doc"), Some("plain text"));
+        assert_eq!(parsed.files.get("img"), Some("data:image/png;base64,iVBORw=="));
+    }
+
+    #[test]
+    fn test_parse_binary_body() {
+        let req = Request::new("PUT", "/put").with_body(vec![0xff, 0x00, 0x10]);
+
+        let parsed = parse_body(&req);
+        assert_eq!(parsed.data, "data:application/octet-stream;base64,/wAQ");
+    }
+}
@@ -4,11 +4,9 @@ use serde_json::json;
 /// Handles /cache endpoint
 /// Returns 304 if If-Modified-Since or If-None-Match provided
 pub fn cache_handler(req: &Request) -> Response {
-    let has_if_modified_since = req.headers.contains_key("If-Modified-Since") 
-        || req.headers.contains_key("if-modified-since");
+    let has_if_modified_since = req.headers.contains_key("If-Modified-Since");
     
-    let has_if_none_match = req.headers.contains_key("If-None-Match")
-        || req.headers.contains_key("if-none-match");
+    let has_if_none_match = req.headers.contains_key("If-None-Match");
     
     if has_if_modified_since || has_if_none_match {
         // Return 304 Not Modified
@@ -17,7 +15,7 @@ pub fn cache_handler(req: &Request) -> Response {
         // Return normal response with cache headers
         let response_data = json!({
             "headers": req.headers,
-            "url": format!("https://httpbin.org{}", req.path)
+            "url": req.url()
         });
         
         let mut response = Response::new(200);
@@ -31,12 +29,14 @@ pub fn cache_handler(req: &Request) -> Response {
 /// Handles /cache/{n} endpoint
 /// Sets Cache-Control header for n seconds
 pub fn cache_n_handler(req: &Request) -> Response {
-    let n = crate::extract_param::<u64>(&req.path, r"/(?:h[123]/)?cache/(\d+)")
-        .unwrap_or(60);
+    let n = match req.parse_param::<u64>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
     
     let response_data = json!({
         "headers": req.headers,
-        "url": format!("https://httpbin.org{}", req.path)
+        "url": req.url()
     });
     
     let mut response = Response::new(200);
@@ -48,16 +48,15 @@ pub fn cache_n_handler(req: &Request) -> Response {
 /// Handles /etag/{etag} endpoint
 /// Tests ETag validation with If-Match/If-None-Match
 pub fn etag_handler(req: &Request) -> Response {
-    let etag = crate::extract_params(&req.path, r"/(?:h[123]/)?etag/(.+)")
-        .and_then(|v| v.into_iter().next())
-        .unwrap_or_else(|| "default-etag".to_string());
+    let Some(etag) = req.param("etag") else {
+        return Response::new(400).with_text("Missing etag");
+    };
     
     let etag_quoted = format!("\"{}\"", etag);
     
     // Check If-None-Match header
-    if let Some(if_none_match) = req.headers.get("If-None-Match")
-        .or_else(|| req.headers.get("if-none-match")) {
-        if if_none_match == &etag_quoted || if_none_match == "*" {
+    if let Some(if_none_match) = req.headers.get("If-None-Match") {
+        if if_none_match == etag_quoted || if_none_match == "*" {
             // ETag matches - return 304
             let mut response = Response::new(304);
             response.headers.insert("ETag".to_string(), etag_quoted);
@@ -66,9 +65,8 @@ pub fn etag_handler(req: &Request) -> Response {
     }
     
     // Check If-Match header
-    if let Some(if_match) = req.headers.get("If-Match")
-        .or_else(|| req.headers.get("if-match")) {
-        if if_match != &etag_quoted && if_match != "*" {
+    if let Some(if_match) = req.headers.get("If-Match") {
+        if if_match != etag_quoted && if_match != "*" {
             // ETag doesn't match - return 412 Precondition Failed
             return Response::new(412);
         }
@@ -77,7 +75,7 @@ pub fn etag_handler(req: &Request) -> Response {
     // Normal response
     let response_data = json!({
         "headers": req.headers,
-        "url": format!("https://httpbin.org{}", req.path)
+        "url": req.url()
     });
     
     let mut response = Response::new(200);
@@ -89,16 +87,10 @@ pub fn etag_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_cache_handler_no_cache() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/cache".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/cache");
         
         let response = cache_handler(&req);
         assert_eq!(response.status, 200);
@@ -107,15 +99,8 @@ mod tests {
     
     #[test]
     fn test_cache_handler_with_cache() {
-        let mut headers = HashMap::new();
-        headers.insert("If-None-Match".to_string(), "\"sample-etag\"".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/cache".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/cache")
+            .with_header("If-None-Match", "\"sample-etag\"");
         
         let response = cache_handler(&req);
         assert_eq!(response.status, 304);
@@ -123,29 +108,19 @@ mod tests {
     
     #[test]
     fn test_cache_n_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/cache/3600".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/cache/3600").with_param("n", "3600");
         
         let response = cache_n_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Cache-Control"), Some(&"public, max-age=3600".to_string()));
+        assert_eq!(response.headers.get("Cache-Control"), Some("public, max-age=3600"));
     }
     
     #[test]
     fn test_etag_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/etag/test123".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/etag/test123").with_param("etag", "test123");
         
         let response = etag_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("ETag"), Some(&"\"test123\"".to_string()));
+        assert_eq!(response.headers.get("ETag"), Some("\"test123\""));
     }
 }
@@ -0,0 +1,273 @@
+//! Client address resolution behind trusted reverse proxies
+//!
+//! `X-Forwarded-For`, RFC 7239 `Forwarded` and `X-Real-IP` are only believed
+//! when the connection comes from an address in the trusted-proxy list;
+//! otherwise the socket peer is the client.
+
+use crate::Headers;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// An address range in CIDR notation, e.g. `10.0.0.0/8` or `::1/128`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, canonical(ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_matches(&net.octets(), &ip.octets(), self.prefix)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_matches(&net.octets(), &ip.octets(), self.prefix)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    /// Parse `addr/prefix`, or a bare address as a single-host network
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+
+        let addr: IpAddr = addr
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid address in '{}'", s))?;
+        let addr = canonical(addr);
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+
+        let prefix = match prefix {
+            Some(p) => p
+                .trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|p| *p <= max)
+                .ok_or_else(|| format!("invalid prefix length in '{}'", s))?,
+            None => max,
+        };
+
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// Proxies whose forwarding headers are trusted
+///
+/// Empty by default, meaning forwarding headers are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies(Vec<IpNetwork>);
+
+impl TrustedProxies {
+    pub fn new(networks: Vec<IpNetwork>) -> Self {
+        Self(networks)
+    }
+
+    /// Parse a list of CIDR ranges or bare addresses
+    pub fn parse<S: AsRef<str>>(entries: &[S]) -> Result<Self, String> {
+        entries
+            .iter()
+            .map(|entry| entry.as_ref().parse())
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|net| net.contains(ip))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Resolve the originating client address for a connection from `peer`
+///
+/// When `peer` is a trusted proxy, the forwarding chain (`Forwarded`, else
+/// `X-Forwarded-For`, else `X-Real-IP`) is walked from the nearest hop
+/// outwards and the first address that is not itself a trusted proxy wins.
+/// Unparseable entries (`unknown`, obfuscated identifiers) stop the walk.
+pub fn resolve_client_ip(peer: IpAddr, headers: &Headers, trusted: &TrustedProxies) -> IpAddr {
+    let mut client = canonical(peer);
+
+    if !trusted.contains(client) {
+        return client;
+    }
+
+    for hop in forwarding_chain(headers).iter().rev() {
+        match parse_node(hop) {
+            Some(ip) => {
+                client = ip;
+                if !trusted.contains(ip) {
+                    break;
+                }
+            }
+            None => break,
+        }
+    }
+
+    client
+}
+
+/// Forwarded-for addresses, client first
+fn forwarding_chain(headers: &Headers) -> Vec<String> {
+    let forwarded: Vec<String> = headers
+        .get_all("Forwarded")
+        .flat_map(|value| value.split(','))
+        .filter_map(forwarded_for)
+        .collect();
+
+    if !forwarded.is_empty() {
+        return forwarded;
+    }
+
+    let x_forwarded_for: Vec<String> = headers
+        .get_all("X-Forwarded-For")
+        .flat_map(|value| value.split(','))
+        .map(|hop| hop.trim().to_string())
+        .filter(|hop| !hop.is_empty())
+        .collect();
+
+    if !x_forwarded_for.is_empty() {
+        return x_forwarded_for;
+    }
+
+    headers
+        .get("X-Real-IP")
+        .map(|ip| vec![ip.trim().to_string()])
+        .unwrap_or_default()
+}
+
+/// The `for=` parameter of one `Forwarded` element (RFC 7239 section 4)
+fn forwarded_for(element: &str) -> Option<String> {
+    element
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("for"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+}
+
+/// Parse a node identifier: `1.2.3.4`, `1.2.3.4:80`, `[2001:db8::1]:80` or `2001:db8::1`
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let ip = if let Some(rest) = node.strip_prefix('[') {
+        rest.split_once(']')?.0.parse().ok()?
+    } else if let Ok(ip) = node.parse() {
+        ip
+    } else {
+        // IPv4 with a port
+        node.rsplit_once(':')?.0.parse::<std::net::Ipv4Addr>().ok()?.into()
+    };
+
+    Some(canonical(ip))
+}
+
+/// Unwrap IPv4-mapped IPv6 addresses (::ffff:a.b.c.d) to plain IPv4
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        ip => ip,
+    }
+}
+
+fn prefix_matches(net: &[u8], ip: &[u8], prefix: u8) -> bool {
+    let full = (prefix / 8) as usize;
+    let rest = prefix % 8;
+
+    if net[..full] != ip[..full] {
+        return false;
+    }
+    if rest == 0 {
+        return true;
+    }
+    let mask = 0xffu8 << (8 - rest);
+    net[full] & mask == ip[full] & mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_network_contains() {
+        let net: IpNetwork = "10.1.0.0/16".parse().unwrap();
+        assert!(net.contains(ip("10.1.200.3")));
+        assert!(!net.contains(ip("10.2.0.1")));
+        assert!(net.contains(ip("::ffff:10.1.0.9")));
+
+        let net: IpNetwork = "2001:db8::/33".parse().unwrap();
+        assert!(net.contains(ip("2001:db8:7fff::1")));
+        assert!(!net.contains(ip("2001:db8:8000::1")));
+
+        assert!("10.0.0.0/33".parse::<IpNetwork>().is_err());
+        assert!("nope".parse::<IpNetwork>().is_err());
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_headers() {
+        let headers: Headers = [("X-Forwarded-For", "203.0.113.9")].into_iter().collect();
+        let trusted = TrustedProxies::parse(&["10.0.0.0/8"]).unwrap();
+
+        assert_eq!(resolve_client_ip(ip("198.51.100.1"), &headers, &trusted), ip("198.51.100.1"));
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &headers, &TrustedProxies::default()),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn test_x_forwarded_for_chain() {
+        let headers: Headers = [
+            ("X-Forwarded-For", "203.0.113.9, 198.51.100.7"),
+            ("X-Forwarded-For", "10.0.0.2"),
+        ]
+        .into_iter()
+        .collect();
+        let trusted = TrustedProxies::parse(&["10.0.0.0/8"]).unwrap();
+
+        // 198.51.100.7 is the first untrusted hop; a spoofed left-most entry is ignored
+        assert_eq!(resolve_client_ip(ip("10.0.0.1"), &headers, &trusted), ip("198.51.100.7"));
+
+        let trusted = TrustedProxies::parse(&["10.0.0.0/8", "198.51.100.7"]).unwrap();
+        assert_eq!(resolve_client_ip(ip("10.0.0.1"), &headers, &trusted), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn test_forwarded_header() {
+        let headers: Headers = [
+            ("Forwarded", r#"for="[2001:db8:cafe::17]:4711";proto=https, for=192.0.2.60:80"#),
+            ("X-Forwarded-For", "203.0.113.9"),
+        ]
+        .into_iter()
+        .collect();
+        let trusted = TrustedProxies::parse(&["::1", "192.0.2.60"]).unwrap();
+
+        assert_eq!(resolve_client_ip(ip("::1"), &headers, &trusted), ip("2001:db8:cafe::17"));
+    }
+
+    #[test]
+    fn test_unknown_node_stops_walk() {
+        let headers: Headers = [("Forwarded", "for=unknown, for=192.0.2.60")].into_iter().collect();
+        let trusted = TrustedProxies::parse(&["127.0.0.1", "192.0.2.60"]).unwrap();
+
+        assert_eq!(resolve_client_ip(ip("127.0.0.1"), &headers, &trusted), ip("192.0.2.60"));
+    }
+}
@@ -55,8 +55,8 @@ pub fn brotli_handler(req: &Request) -> Response {
 fn get_sample_data(req: &Request) -> String {
     serde_json::json!({
         "headers": req.headers,
-        "origin": crate::get_client_ip(&req.headers).unwrap_or_else(|| "127.0.0.1".to_string()),
-        "url": format!("https://httpbin.org{}", req.path),
+        "origin": req.origin(),
+        "url": req.url(),
         "gzipped": true,
         "deflated": true,
         "brotli": true,
@@ -67,50 +67,34 @@ fn get_sample_data(req: &Request) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_gzip_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/gzip".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/gzip");
         
         let response = gzip_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Encoding"), Some(&"gzip".to_string()));
+        assert_eq!(response.headers.get("Content-Encoding"), Some("gzip"));
         assert!(!response.body.is_empty());
     }
     
     #[test]
     fn test_deflate_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/deflate".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/deflate");
         
         let response = deflate_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Encoding"), Some(&"deflate".to_string()));
+        assert_eq!(response.headers.get("Content-Encoding"), Some("deflate"));
         assert!(!response.body.is_empty());
     }
     
     #[test]
     fn test_brotli_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/brotli".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/brotli");
         
         let response = brotli_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Encoding"), Some(&"br".to_string()));
+        assert_eq!(response.headers.get("Content-Encoding"), Some("br"));
         assert!(!response.body.is_empty());
     }
 }
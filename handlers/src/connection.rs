@@ -0,0 +1,108 @@
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::net::SocketAddr;
+
+/// HTTP protocol version a request was received over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HttpVersion {
+    Http09,
+    Http10,
+    #[default]
+    Http11,
+    Http2,
+    Http3,
+}
+
+impl HttpVersion {
+    /// Version as written on an HTTP/1.x request line (e.g. "HTTP/1.1", "HTTP/2")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpVersion::Http09 => "HTTP/0.9",
+            HttpVersion::Http10 => "HTTP/1.0",
+            HttpVersion::Http11 => "HTTP/1.1",
+            HttpVersion::Http2 => "HTTP/2",
+            HttpVersion::Http3 => "HTTP/3",
+        }
+    }
+}
+
+impl fmt::Display for HttpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for HttpVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Details about the connection a request arrived on
+///
+/// Fields are None when the transport does not provide them: TLS details on
+/// cleartext connections, or the stream id on HTTP/1.x and HTTP/2.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConnectionInfo {
+    pub peer_addr: Option<SocketAddr>,
+    /// Negotiated ALPN protocol id, e.g. "h2"
+    pub alpn: Option<String>,
+    /// Negotiated TLS version, e.g. "TLSv1.3"
+    pub tls_version: Option<String>,
+    pub cipher_suite: Option<String>,
+    /// TLS SNI server name sent by the client
+    pub sni: Option<String>,
+    /// Whether the TLS session was resumed
+    pub resumed: Option<bool>,
+    /// Whether the request arrived as 0-RTT early data
+    pub early_data: Option<bool>,
+    /// HTTP/3 request stream id
+    pub stream_id: Option<u64>,
+    /// DER certificate chain the client presented over TLS, leaf first
+    #[serde(skip)]
+    pub peer_certificates: Vec<Vec<u8>>,
+}
+
+impl ConnectionInfo {
+    pub fn is_tls(&self) -> bool {
+        self.tls_version.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_display() {
+        assert_eq!(HttpVersion::Http11.to_string(), "HTTP/1.1");
+        assert_eq!(HttpVersion::Http3.as_str(), "HTTP/3");
+        assert_eq!(serde_json::to_value(HttpVersion::Http2).unwrap(), "HTTP/2");
+    }
+
+    #[test]
+    fn test_connection_info_serialize() {
+        let info = ConnectionInfo {
+            peer_addr: Some("[::1]:4433".parse().unwrap()),
+            alpn: Some("h3".to_string()),
+            tls_version: Some("TLSv1.3".to_string()),
+            stream_id: Some(4),
+            ..ConnectionInfo::default()
+        };
+
+        assert!(info.is_tls());
+        assert_eq!(
+            serde_json::to_value(&info).unwrap(),
+            serde_json::json!({
+                "peer_addr": "[::1]:4433",
+                "alpn": "h3",
+                "tls_version": "TLSv1.3",
+                "cipher_suite": null,
+                "sni": null,
+                "resumed": null,
+                "early_data": null,
+                "stream_id": 4
+            })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Request, Response};
+use crate::{mount_prefix, Request, Response};
 use serde_json::json;
 
 /// Handles /cookies endpoint
@@ -16,67 +16,64 @@ pub fn cookies_handler(req: &Request) -> Response {
 /// Handles /cookies/set endpoint
 /// Sets cookies from query parameters
 pub fn cookies_set_handler(req: &Request) -> Response {
-    let query = crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""));
+    let query = req.args();
     
     let mut response = Response::new(302);
     
     // Set cookies from query parameters
     for (key, value) in query.iter() {
         let cookie = format!("{}={}", key, value);
-        response.headers.insert("Set-Cookie".to_string(), cookie);
+        response.headers.append("Set-Cookie", cookie);
     }
     
     // Redirect to /cookies to show the set cookies
-    response.headers.insert("Location".to_string(), "/cookies".to_string());
+    response.headers.insert("Location".to_string(), cookies_location(req));
     response
 }
 
 /// Handles /cookies/delete endpoint
 /// Deletes cookies specified in query parameters
 pub fn cookies_delete_handler(req: &Request) -> Response {
-    let query = crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""));
+    let query = req.args();
     
     let mut response = Response::new(302);
     
     // Delete cookies by setting them with Max-Age=0
     for key in query.keys() {
         let cookie = format!("{}=deleted; Max-Age=0", key);
-        response.headers.insert("Set-Cookie".to_string(), cookie);
+        response.headers.append("Set-Cookie", cookie);
     }
     
     // Redirect to /cookies
-    response.headers.insert("Location".to_string(), "/cookies".to_string());
+    response.headers.insert("Location".to_string(), cookies_location(req));
     response
 }
 
 /// Handles /cookies/set/{name}/{value} endpoint
 /// Sets a specific cookie
 pub fn cookies_set_specific_handler(req: &Request) -> Response {
-    let params = crate::extract_params(&req.path, r"/(?:h[123]/)?cookies/set/([^/]+)/([^/?]+)")
-        .unwrap_or_default();
-    
-    if params.len() < 2 {
+    let (Some(name), Some(value)) = (req.param("name"), req.param("value")) else {
         return Response::new(400).with_text("Invalid cookie parameters");
-    }
-    
-    let name = &params[0];
-    let value = &params[1];
+    };
     
     let mut response = Response::new(302);
     response.headers.insert("Set-Cookie".to_string(), format!("{}={}", name, value));
-    response.headers.insert("Location".to_string(), "/cookies".to_string());
+    response.headers.insert("Location".to_string(), cookies_location(req));
     response
 }
 
-/// Parse cookies from Cookie header
-fn parse_cookies(headers: &std::collections::HashMap<String, String>) -> std::collections::HashMap<String, String> {
-    let cookie_header = headers.get("Cookie")
-        .or_else(|| headers.get("cookie"))
-        .cloned()
-        .unwrap_or_default();
-    
-    cookie_header
-        .split(';')
+/// `/cookies` under the mount prefix the request came through
+fn cookies_location(req: &Request) -> String {
+    format!("{}/cookies", mount_prefix(&req.path, "/cookies/"))
+}
+
+/// Parse cookies from Cookie headers
+///
+/// HTTP/2 and HTTP/3 clients may split cookies across several headers.
+fn parse_cookies(headers: &crate::Headers) -> std::collections::HashMap<String, String> {
+    headers
+        .get_all("Cookie")
+        .flat_map(|header| header.split(';'))
         .filter_map(|pair| {
             let mut parts = pair.trim().splitn(2, '=');
             let key = parts.next()?.trim().to_string();
@@ -89,46 +86,57 @@ fn parse_cookies(headers: &std::collections::HashMap<String, String>) -> std::co
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_cookies_handler() {
-        let mut headers = HashMap::new();
-        headers.insert("Cookie".to_string(), "foo=bar; baz=qux".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/cookies".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/cookies")
+            .with_header("Cookie", "foo=bar; baz=qux");
         
         let response = cookies_handler(&req);
         assert_eq!(response.status, 200);
     }
     
+    #[test]
+    fn test_cookies_handler_split_headers() {
+        let req = Request::new("GET", "/cookies")
+            .with_header("cookie", "a=1")
+            .with_header("cookie", "b=2; c=3");
+        
+        let response = cookies_handler(&req);
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["cookies"], json!({"a": "1", "b": "2", "c": "3"}));
+    }
+    
     #[test]
     fn test_cookies_set_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/cookies/set?foo=bar&baz=qux".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/cookies/set?foo=bar&baz=qux");
         
         let response = cookies_set_handler(&req);
         assert_eq!(response.status, 302);
-        assert!(response.headers.contains_key("Set-Cookie"));
+        assert_eq!(
+            response.headers.get_all("Set-Cookie").collect::<Vec<_>>(),
+            vec!["foo=bar", "baz=qux"]
+        );
+        assert_eq!(response.headers.get("Location"), Some("/cookies"));
+    }
+
+    #[test]
+    fn test_cookies_redirects_keep_mount_prefix() {
+        let req = Request::new("GET", "/h2/cookies/set?foo=bar");
+        assert_eq!(cookies_set_handler(&req).headers.get("Location"), Some("/h2/cookies"));
+
+        let req = Request::new("GET", "/h2/cookies/delete?foo");
+        assert_eq!(cookies_delete_handler(&req).headers.get("Location"), Some("/h2/cookies"));
+
+        let req = Request::new("GET", "/h1h2/cookies/set/a/b")
+            .with_param("name", "a")
+            .with_param("value", "b");
+        assert_eq!(cookies_set_specific_handler(&req).headers.get("Location"), Some("/h1h2/cookies"));
     }
     
     #[test]
     fn test_cookies_delete_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/cookies/delete?foo&baz".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/cookies/delete?foo&baz");
         
         let response = cookies_delete_handler(&req);
         assert_eq!(response.status, 302);
@@ -1,16 +1,17 @@
-use crate::{Request, Response};
+use crate::{mount_prefix, Request, Response};
 
 /// Handles /forms/post endpoint
-/// HTML form that posts to /post
-pub fn forms_post_handler(_req: &Request) -> Response {
-    let html = r#"<!DOCTYPE html>
+/// HTML form that posts to /post, under the same mount prefix
+pub fn forms_post_handler(req: &Request) -> Response {
+    let prefix = mount_prefix(&req.path, "/forms/");
+    let html = format!(r#"<!DOCTYPE html>
 <html>
 <head>
     <title>Form Test</title>
 </head>
 <body>
     <h1>HTML Form</h1>
-    <form method="POST" action="/post">
+    <form method="POST" action="{}/post">
         <div>
             <label for="custname">Name:</label>
             <input type="text" id="custname" name="custname" required>
@@ -40,10 +41,10 @@ pub fn forms_post_handler(_req: &Request) -> Response {
         </div>
     </form>
 </body>
-</html>"#;
+</html>"#, prefix);
     
     let mut response = Response::new(200);
-    response.body = html.as_bytes().to_vec();
+    response.body = html.into_bytes();
     response.headers.insert("Content-Type".to_string(), "text/html; charset=utf-8".to_string());
     response
 }
@@ -51,13 +52,13 @@ pub fn forms_post_handler(_req: &Request) -> Response {
 /// Handles /response-headers endpoint
 /// Returns custom response headers specified in query parameters
 pub fn response_headers_handler(req: &Request) -> Response {
-    let query = crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""));
+    let query = req.args();
     
     let mut response = Response::new(200);
     
     // Add all query parameters as response headers
     for (key, value) in query.iter() {
-        response.headers.insert(key.clone(), value.clone());
+        response.headers.append(key, value);
     }
     
     // Also return the headers in the body
@@ -73,36 +74,29 @@ pub fn response_headers_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_forms_post_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/forms/post".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/forms/post");
         
         let response = forms_post_handler(&req);
         assert_eq!(response.status, 200);
         let body = String::from_utf8(response.body).unwrap();
         assert!(body.contains("<form"));
-        assert!(body.contains("method=\"POST\""));
+        assert!(body.contains("method=\"POST\" action=\"/post\""));
+
+        let response = forms_post_handler(&Request::new("GET", "/h1/forms/post"));
+        let body = String::from_utf8(response.body).unwrap();
+        assert!(body.contains("action=\"/h1/post\""));
     }
     
     #[test]
     fn test_response_headers_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/response-headers?X-Custom-Header=value&X-Another=test".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/response-headers?X-Custom-Header=value&X-Another=test");
         
         let response = response_headers_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("X-Custom-Header"), Some(&"value".to_string()));
-        assert_eq!(response.headers.get("X-Another"), Some(&"test".to_string()));
+        assert_eq!(response.headers.get("X-Custom-Header"), Some("value"));
+        assert_eq!(response.headers.get("X-Another"), Some("test"));
     }
 }
@@ -0,0 +1,112 @@
+//! Common interface over sync and async endpoint handlers
+//!
+//! Most handlers are plain `fn(&Request) -> Response`. Handlers that need to
+//! wait (e.g. /delay) are `async fn(Request) -> Response`. Both convert into a
+//! [`Handler`] with [`IntoHandler`], so the server adapter drives every
+//! endpoint the same way.
+
+use crate::{Request, Response};
+use std::future::Future;
+use std::pin::Pin;
+
+/// Boxed, sendable future as returned by [`Handler::call`]
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// An endpoint handler
+pub trait Handler: Send + Sync + 'static {
+    fn call(&self, req: Request) -> BoxFuture<Response>;
+}
+
+/// Conversion of handler functions into a [`Handler`]
+///
+/// `Marker` only exists to keep the sync and async impls apart; callers never
+/// name it.
+pub trait IntoHandler<Marker> {
+    type Handler: Handler;
+
+    fn into_handler(self) -> Self::Handler;
+}
+
+/// Marker for `fn(&Request) -> Response`
+pub struct SyncMarker;
+
+/// Marker for `async fn(Request) -> Response`
+pub struct AsyncMarker;
+
+/// A synchronous handler function
+pub struct SyncHandler<F>(F);
+
+/// An asynchronous handler function
+pub struct AsyncHandler<F>(F);
+
+impl<F> Handler for SyncHandler<F>
+where
+    F: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    fn call(&self, req: Request) -> BoxFuture<Response> {
+        Box::pin(std::future::ready((self.0)(&req)))
+    }
+}
+
+impl<F, Fut> Handler for AsyncHandler<F>
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send + 'static,
+{
+    fn call(&self, req: Request) -> BoxFuture<Response> {
+        Box::pin((self.0)(req))
+    }
+}
+
+impl<F> IntoHandler<SyncMarker> for F
+where
+    F: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    type Handler = SyncHandler<F>;
+
+    fn into_handler(self) -> Self::Handler {
+        SyncHandler(self)
+    }
+}
+
+impl<F, Fut> IntoHandler<AsyncMarker> for F
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send + 'static,
+{
+    type Handler = AsyncHandler<F>;
+
+    fn into_handler(self) -> Self::Handler {
+        AsyncHandler(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sync_handler(req: &Request) -> Response {
+        Response::new(200).with_text(&req.path)
+    }
+
+    async fn async_handler(req: Request) -> Response {
+        tokio::task::yield_now().await;
+        Response::new(201).with_text(&req.path)
+    }
+
+    #[tokio::test]
+    async fn test_sync_and_async_handlers() {
+        let handlers: Vec<Box<dyn Handler>> = vec![
+            Box::new(sync_handler.into_handler()),
+            Box::new(async_handler.into_handler()),
+        ];
+
+        let mut statuses = Vec::new();
+        for handler in &handlers {
+            let response = handler.call(Request::new("GET", "/x")).await;
+            assert_eq!(response.body, b"/x");
+            statuses.push(response.status);
+        }
+        assert_eq!(statuses, vec![200, 201]);
+    }
+}
@@ -0,0 +1,167 @@
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::HashMap;
+
+/// HTTP header map
+///
+/// Names are matched case-insensitively, insertion order is kept and a
+/// name may carry several values (e.g. repeated `Set-Cookie`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers(Vec<(String, String)>);
+
+impl Headers {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// First value for a header
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// All values for a header, in order of appearance
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.0.iter().any(|(k, _)| k.eq_ignore_ascii_case(name))
+    }
+
+    /// Set a header, replacing any existing values
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+
+        // Keep the position of the first existing value
+        let first = self.0.iter().position(|(k, _)| k.eq_ignore_ascii_case(&name));
+        self.remove(&name);
+
+        match first {
+            Some(i) => self.0.insert(i, (name, value)),
+            None => self.0.push((name, value)),
+        }
+    }
+
+    /// Add a header value, keeping existing values
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.push((name.into(), value.into()));
+    }
+
+    /// Remove all values for a header
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|(k, _)| !k.eq_ignore_ascii_case(name));
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// All (name, value) pairs, repeated names included
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Distinct header names (first spelling seen), in order of appearance
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (k, _) in &self.0 {
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(k)) {
+                names.push(k);
+            }
+        }
+        names
+    }
+
+    /// Values for a header joined with ", " (RFC 9110 list syntax)
+    pub fn get_joined(&self, name: &str) -> Option<String> {
+        let values: Vec<&str> = self.get_all(name).collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.join(", "))
+        }
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Headers {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+/// Serializes as a JSON object, one entry per name with repeated values joined
+impl Serialize for Headers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names = self.names();
+        let mut map = serializer.serialize_map(Some(names.len()))?;
+        for name in names {
+            map.serialize_entry(name, &self.get_joined(name).unwrap_or_default())?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Headers {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = HashMap::<String, String>::deserialize(deserializer)?;
+        Ok(map.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_insensitive_lookup() {
+        let mut headers = Headers::new();
+        headers.insert("Content-Type", "text/plain");
+
+        assert_eq!(headers.get("content-type"), Some("text/plain"));
+        assert!(headers.contains_key("CONTENT-TYPE"));
+    }
+
+    #[test]
+    fn test_insert_replaces_all_values() {
+        let mut headers = Headers::new();
+        headers.append("X-A", "1");
+        headers.append("Accept", "*/*");
+        headers.append("x-a", "2");
+        headers.insert("X-a", "3");
+
+        assert_eq!(headers.get_all("x-a").collect::<Vec<_>>(), vec!["3"]);
+        assert_eq!(headers.names(), vec!["X-a", "Accept"]);
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        let mut headers = Headers::new();
+        headers.append("Set-Cookie", "a=1");
+        headers.append("set-cookie", "b=2");
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers.get("Set-Cookie"), Some("a=1"));
+        assert_eq!(
+            serde_json::to_value(&headers).unwrap(),
+            serde_json::json!({"Set-Cookie": "a=1, b=2"})
+        );
+
+        headers.remove("SET-COOKIE");
+        assert!(headers.is_empty());
+    }
+}
@@ -4,10 +4,14 @@ use crate::{Request, Response, HttpBinResponse};
 /// Returns GET request data
 pub fn get_handler(req: &Request) -> Response {
     let response_data = HttpBinResponse {
-        args: Some(crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""))),
+        args: Some(req.args().to_json_map()),
+        data: None,
+        files: None,
+        form: None,
         headers: Some(req.headers.clone()),
-        url: Some(format!("https://httpbin.org{}", req.path)),
-        origin: crate::get_client_ip(&req.headers),
+        json: None,
+        url: Some(req.url()),
+        origin: req.origin(),
     };
     
     Response::new(200).with_json(&response_data)
@@ -16,67 +20,48 @@ pub fn get_handler(req: &Request) -> Response {
 /// Handles /post endpoint
 /// Returns POST request data
 pub fn post_handler(req: &Request) -> Response {
-    let response_data = HttpBinResponse {
-        args: Some(crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""))),
-        headers: Some(req.headers.clone()),
-        url: Some(format!("https://httpbin.org{}", req.path)),
-        origin: crate::get_client_ip(&req.headers),
-    };
-    
-    // TODO: Parse body data (will implement later)
-    
-    Response::new(200).with_json(&response_data)
+    Response::new(200).with_json(&body_echo(req))
 }
 
 /// Handles /put endpoint
 pub fn put_handler(req: &Request) -> Response {
-    let response_data = HttpBinResponse {
-        args: Some(crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""))),
-        headers: Some(req.headers.clone()),
-        url: Some(format!("https://httpbin.org{}", req.path)),
-        origin: crate::get_client_ip(&req.headers),
-    };
-    
-    Response::new(200).with_json(&response_data)
+    Response::new(200).with_json(&body_echo(req))
 }
 
 /// Handles /patch endpoint
 pub fn patch_handler(req: &Request) -> Response {
-    let response_data = HttpBinResponse {
-        args: Some(crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""))),
-        headers: Some(req.headers.clone()),
-        url: Some(format!("https://httpbin.org{}", req.path)),
-        origin: crate::get_client_ip(&req.headers),
-    };
-    
-    Response::new(200).with_json(&response_data)
+    Response::new(200).with_json(&body_echo(req))
 }
 
 /// Handles /delete endpoint
 pub fn delete_handler(req: &Request) -> Response {
-    let response_data = HttpBinResponse {
-        args: Some(crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""))),
+    Response::new(200).with_json(&body_echo(req))
+}
+
+/// Request data plus the decoded body, shared by the body-carrying methods
+fn body_echo(req: &Request) -> HttpBinResponse {
+    let body = crate::body::parse_body(req);
+
+    HttpBinResponse {
+        args: Some(req.args().to_json_map()),
+        data: Some(body.data),
+        files: Some(body.files.to_json_map()),
+        form: Some(body.form.to_json_map()),
         headers: Some(req.headers.clone()),
-        url: Some(format!("https://httpbin.org{}", req.path)),
-        origin: crate::get_client_ip(&req.headers),
-    };
-    
-    Response::new(200).with_json(&response_data)
+        json: Some(body.json),
+        url: Some(req.url()),
+        origin: req.origin(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use serde_json::Value;
     
     #[test]
     fn test_get_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/get?foo=bar".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/get?foo=bar");
         
         let response = get_handler(&req);
         assert_eq!(response.status, 200);
@@ -84,15 +69,80 @@ mod tests {
     }
     
     #[test]
-    fn test_post_handler() {
+    fn test_get_handler_args() {
+        let req = Request::new("GET", "/get?tag=a&tag=b&q=hello%20world");
+        
+        let response = get_handler(&req);
+        let body: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["args"]["tag"], serde_json::json!(["a", "b"]));
+        assert_eq!(body["args"]["q"], "hello world");
+        assert_eq!(body["url"], "https://httpbin.org/get?tag=a&tag=b&q=hello%20world");
+    }
+
+    #[test]
+    fn test_configured_base_url() {
         let req = Request {
-            method: "POST".to_string(),
-            path: "/post".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
+            settings: std::sync::Arc::new(crate::EndpointSettings {
+                base_url: "http://mock.test:8080".to_string(),
+                ..Default::default()
+            }),
+            ..Request::new("GET", "/get?a=1")
         };
+
+        let body: Value = serde_json::from_slice(&get_handler(&req).body).unwrap();
+        assert_eq!(body["url"], "http://mock.test:8080/get?a=1");
+    }
+    
+    #[test]
+    fn test_post_handler() {
+        let req = Request::new("POST", "/post");
         
         let response = post_handler(&req);
         assert_eq!(response.status, 200);
     }
+    
+    #[test]
+    fn test_post_handler_echoes_json() {
+        let req = Request::new("POST", "/post")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"name": "orb"}"#);
+        
+        let response = post_handler(&req);
+        let body: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["data"], r#"{"name": "orb"}"#);
+        assert_eq!(body["json"]["name"], "orb");
+        assert_eq!(body["form"], serde_json::json!({}));
+    }
+    
+    #[test]
+    fn test_put_handler_echoes_form() {
+        let req = Request::new("PUT", "/put")
+            .with_header("content-type", "application/x-www-form-urlencoded")
+            .with_body("a=1&b=two+words");
+        
+        let response = put_handler(&req);
+        let body: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["data"], "");
+        assert_eq!(body["form"]["b"], "two words");
+        assert_eq!(body["json"], Value::Null);
+    }
+    
+    #[test]
+    fn test_patch_handler_echoes_multipart() {
+        let req = Request::new("PATCH", "/patch")
+            .with_header("Content-Type", "multipart/form-data; boundary=--sep")
+            .with_body(
+                "----sep\r\n\
+Content-Disposition: form-data; name=\"title\"\r\n\r\nreport\r\n\
+----sep\r\n\
+Content-Disposition: form-data; name=\"file\"; filename=\"r.csv\"\r\n\r\na,b\r\n\
+----sep--\r\n",
+            );
+        
+        let response = patch_handler(&req);
+        let body: Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["form"]["title"], "report");
+        assert_eq!(body["files"]["file"], "a,b");
+        assert_eq!(body["data"], "");
+    }
 }
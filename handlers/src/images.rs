@@ -4,7 +4,6 @@ use crate::{Request, Response};
 /// Returns image based on Accept header
 pub fn image_handler(req: &Request) -> Response {
     let accept = req.headers.get("Accept")
-        .or_else(|| req.headers.get("accept"))
         .map(|s| s.to_lowercase())
         .unwrap_or_default();
     
@@ -106,64 +105,41 @@ pub fn image_svg_handler(_req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_image_png_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/image/png".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/image/png");
         
         let response = image_png_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Type"), Some(&"image/png".to_string()));
+        assert_eq!(response.headers.get("Content-Type"), Some("image/png"));
     }
     
     #[test]
     fn test_image_jpeg_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/image/jpeg".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/image/jpeg");
         
         let response = image_jpeg_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Type"), Some(&"image/jpeg".to_string()));
+        assert_eq!(response.headers.get("Content-Type"), Some("image/jpeg"));
     }
     
     #[test]
     fn test_image_svg_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/image/svg".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/image/svg");
         
         let response = image_svg_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Type"), Some(&"image/svg+xml".to_string()));
+        assert_eq!(response.headers.get("Content-Type"), Some("image/svg+xml"));
     }
     
     #[test]
     fn test_image_handler_with_accept() {
-        let mut headers = HashMap::new();
-        headers.insert("Accept".to_string(), "image/webp".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/image".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/image")
+            .with_header("Accept", "image/webp");
         
         let response = image_handler(&req);
         assert_eq!(response.status, 200);
-        assert_eq!(response.headers.get("Content-Type"), Some(&"image/webp".to_string()));
+        assert_eq!(response.headers.get("Content-Type"), Some("image/webp"));
     }
 }
@@ -1,6 +1,8 @@
 use crate::{Request, Response};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::net::IpAddr;
 use uuid::Uuid;
+use x509_parser::prelude::{ASN1Time, FromDer, GeneralName, X509Certificate};
 
 /// Handles /headers endpoint
 /// Returns all request headers
@@ -17,9 +19,7 @@ pub fn headers_handler(req: &Request) -> Response {
 pub fn user_agent_handler(req: &Request) -> Response {
     let user_agent = req.headers
         .get("User-Agent")
-        .or_else(|| req.headers.get("user-agent"))
-        .cloned()
-        .unwrap_or_else(|| "Unknown".to_string());
+        .unwrap_or("Unknown");
     
     let response_data = json!({
         "user-agent": user_agent
@@ -31,16 +31,107 @@ pub fn user_agent_handler(req: &Request) -> Response {
 /// Handles /ip endpoint
 /// Returns origin IP address
 pub fn ip_handler(req: &Request) -> Response {
-    let origin = crate::get_client_ip(&req.headers)
-        .unwrap_or_else(|| "127.0.0.1".to_string());
-    
     let response_data = json!({
-        "origin": origin
+        "origin": req.origin()
     });
     
     Response::new(200).with_json(&response_data)
 }
 
+/// Handles /protocol endpoint
+/// Returns the negotiated protocol and connection details
+pub fn protocol_handler(req: &Request) -> Response {
+    let conn = &req.connection;
+
+    let response_data = json!({
+        "http_version": req.version,
+        "alpn": conn.alpn,
+        "tls": conn.is_tls(),
+        "tls_version": conn.tls_version,
+        "cipher_suite": conn.cipher_suite,
+        "sni": conn.sni,
+        "resumed": conn.resumed,
+        "early_data": conn.early_data,
+        "stream_id": conn.stream_id
+    });
+
+    Response::new(200).with_json(&response_data)
+}
+
+/// Handles /client-cert endpoint
+/// Returns the TLS client certificate chain, leaf first
+pub fn client_cert_handler(req: &Request) -> Response {
+    let chain: Vec<Value> = req
+        .connection
+        .peer_certificates
+        .iter()
+        .map(|der| describe_certificate(der))
+        .collect();
+
+    let response_data = json!({
+        "presented": !chain.is_empty(),
+        "chain": chain
+    });
+
+    Response::new(200).with_json(&response_data)
+}
+
+fn describe_certificate(der: &[u8]) -> Value {
+    let cert = match X509Certificate::from_der(der) {
+        Ok((_, cert)) => cert,
+        Err(e) => return json!({ "error": e.to_string() }),
+    };
+
+    let sans: Vec<String> = match cert.subject_alternative_name() {
+        Ok(Some(ext)) => ext.value.general_names.iter().map(general_name).collect(),
+        _ => Vec::new(),
+    };
+    let validity = cert.validity();
+
+    json!({
+        "subject": cert.subject().to_string(),
+        "issuer": cert.issuer().to_string(),
+        "sans": sans,
+        "serial": cert.raw_serial_as_string(),
+        "not_before": rfc3339(&validity.not_before),
+        "not_after": rfc3339(&validity.not_after)
+    })
+}
+
+/// Subject alternative name in OpenSSL's notation, e.g. "DNS:localhost"
+fn general_name(name: &GeneralName) -> String {
+    match name {
+        GeneralName::DNSName(dns) => format!("DNS:{}", dns),
+        GeneralName::RFC822Name(email) => format!("email:{}", email),
+        GeneralName::URI(uri) => format!("URI:{}", uri),
+        GeneralName::IPAddress(bytes) => {
+            let ip = match bytes.len() {
+                4 => <[u8; 4]>::try_from(*bytes).ok().map(IpAddr::from),
+                16 => <[u8; 16]>::try_from(*bytes).ok().map(IpAddr::from),
+                _ => None,
+            };
+            match ip {
+                Some(ip) => format!("IP:{}", ip),
+                None => name.to_string(),
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+fn rfc3339(time: &ASN1Time) -> String {
+    let dt = time.to_datetime();
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        dt.year(),
+        u8::from(dt.month()),
+        dt.day(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    )
+}
+
 /// Handles /uuid endpoint
 /// Returns a UUID4
 pub fn uuid_handler(_req: &Request) -> Response {
@@ -56,12 +147,9 @@ pub fn uuid_handler(_req: &Request) -> Response {
 /// Handles /base64/{value} endpoint
 /// Decodes base64 value
 pub fn base64_handler(req: &Request) -> Response {
-    // Extract base64 value from path
-    let value = crate::extract_params(&req.path, r"/(?:h[123]/)?base64/(.+)")
-        .and_then(|v| v.into_iter().next())
-        .unwrap_or_default();
-    
-    match crate::decode_base64(&value) {
+    let value = req.param("value").unwrap_or_default();
+
+    match crate::decode_base64(value) {
         Ok(decoded) => {
             Response::new(200).with_text(&String::from_utf8_lossy(&decoded))
         }
@@ -74,32 +162,94 @@ pub fn base64_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use base64::Engine;
+
+    /// DER client certificate (CN=client.test, issued by CN=Test CA, serial 42)
+    const CLIENT_CERT: &str = "\
+        MIIBeDCCAR2gAwIBAgIBKjAKBggqhkjOPQQDAjASMRAwDgYDVQQDDAdUZXN0IENBMB4XDTI1MDEw\
+        MTAwMDAwMFoXDTM1MDEwMTAwMDAwMFowFjEUMBIGA1UEAwwLY2xpZW50LnRlc3QwWTATBgcqhkjO\
+        PQIBBggqhkjOPQMBBwNCAAQCU0l+Ao9hXtY1IysjZdqxfqzZBEic6sZvjiUs4i0a8sUo4WsqYH4k\
+        SUlOCA9w75Vqk9atcfja2FDMITAsvNOyo2AwXjAcBgNVHREEFTATggtjbGllbnQudGVzdIcECgAA\
+        ATAdBgNVHQ4EFgQUceExVDnV3iw/+kj6I/Q/NU7pxxwwHwYDVR0jBBgwFoAUfNeEHemQkIAjR3tM\
+        kG7hBIuS8sMwCgYIKoZIzj0EAwIDSQAwRgIhAKwkXeImRdnrnApLIHdkKI3dzp3fgy0R18YkZwIc\
+        /VctAiEAxeDUF6Zx0GMuik+Tfx6aczgN3vQIACH97l7TEmS8qy8=";
     
     #[test]
     fn test_headers_handler() {
-        let mut headers = HashMap::new();
-        headers.insert("X-Test".to_string(), "value".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/headers".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/headers")
+            .with_header("X-Test", "value");
         
         let response = headers_handler(&req);
         assert_eq!(response.status, 200);
     }
     
     #[test]
-    fn test_uuid_handler() {
+    fn test_ip_handler() {
         let req = Request {
-            method: "GET".to_string(),
-            path: "/uuid".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
+            client_ip: Some("2001:db8::7".parse().unwrap()),
+            ..Request::new("GET", "/ip").with_header("X-Forwarded-For", "203.0.113.9")
         };
+
+        let response = ip_handler(&req);
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["origin"], "2001:db8::7");
+    }
+
+    #[test]
+    fn test_protocol_handler() {
+        let req = Request {
+            version: crate::HttpVersion::Http3,
+            connection: crate::ConnectionInfo {
+                alpn: Some("h3".to_string()),
+                tls_version: Some("TLSv1.3".to_string()),
+                sni: Some("localhost".to_string()),
+                stream_id: Some(8),
+                ..Default::default()
+            },
+            ..Request::new("GET", "/h3/protocol")
+        };
+
+        let response = protocol_handler(&req);
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["http_version"], "HTTP/3");
+        assert_eq!(body["alpn"], "h3");
+        assert_eq!(body["tls"], true);
+        assert_eq!(body["sni"], "localhost");
+        assert_eq!(body["resumed"], serde_json::Value::Null);
+        assert_eq!(body["stream_id"], 8);
+    }
+
+    #[test]
+    fn test_client_cert_handler() {
+        let response = client_cert_handler(&Request::new("GET", "/client-cert"));
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body, json!({ "presented": false, "chain": [] }));
+
+        let req = Request {
+            connection: crate::ConnectionInfo {
+                tls_version: Some("TLSv1.3".to_string()),
+                peer_certificates: vec![base64::engine::general_purpose::STANDARD
+                    .decode(CLIENT_CERT)
+                    .unwrap()],
+                ..Default::default()
+            },
+            ..Request::new("GET", "/h2/client-cert")
+        };
+        let response = client_cert_handler(&req);
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        let leaf = &body["chain"][0];
+        assert_eq!(body["presented"], true);
+        assert_eq!(leaf["subject"], "CN=client.test");
+        assert_eq!(leaf["issuer"], "CN=Test CA");
+        assert_eq!(leaf["sans"], json!(["DNS:client.test", "IP:10.0.0.1"]));
+        assert_eq!(leaf["serial"], "2a");
+        assert_eq!(leaf["not_before"], "2025-01-01T00:00:00Z");
+        assert_eq!(leaf["not_after"], "2035-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_uuid_handler() {
+        let req = Request::new("GET", "/uuid");
         
         let response = uuid_handler(&req);
         assert_eq!(response.status, 200);
@@ -107,12 +257,8 @@ mod tests {
     
     #[test]
     fn test_base64_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/base64/SGVsbG8sIFdvcmxkIQ==".to_string(), // "Hello, World!"
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/base64/SGVsbG8sIFdvcmxkIQ==")
+            .with_param("value", "SGVsbG8sIFdvcmxkIQ=="); // "Hello, World!"
         
         let response = base64_handler(&req);
         assert_eq!(response.status, 200);
@@ -1,7 +1,14 @@
 //! HTTP handler implementations for httpbin endpoints
 
 pub mod types;
+pub mod headers;
+pub mod connection;
+pub mod handler;
+pub mod client_ip;
+pub mod settings;
 pub mod utils;
+pub mod body;
+pub mod multipart;
 pub mod status;
 pub mod http_methods;
 pub mod inspection;
@@ -18,4 +25,9 @@ pub mod auth;
 pub mod forms;
 
 pub use types::*;
+pub use headers::Headers;
+pub use connection::{ConnectionInfo, HttpVersion};
+pub use client_ip::TrustedProxies;
+pub use settings::EndpointSettings;
+pub use handler::{Handler, IntoHandler};
 pub use utils::*;
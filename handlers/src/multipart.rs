@@ -0,0 +1,238 @@
+//! multipart/form-data parsing (RFC 7578)
+
+/// A single part of a multipart/form-data body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    /// Whether this part is a file upload rather than a plain form field
+    pub fn is_file(&self) -> bool {
+        self.filename.is_some()
+    }
+}
+
+/// Extract the boundary parameter from a multipart Content-Type header
+///
+/// Example: boundary("multipart/form-data; boundary=xyz") -> Some("xyz")
+pub fn boundary(content_type: &str) -> Option<String> {
+    let (mime, params) = header_params(content_type);
+
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+
+    params
+        .into_iter()
+        .find(|(key, _)| key == "boundary")
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Split a header value like `form-data; name="a"; filename="b;c.txt"` into
+/// its leading value and `key=value` parameters
+///
+/// Keys are lowercased. Values may be quoted strings (RFC 9110), which can
+/// hold `;` and backslash-escaped characters.
+fn header_params(value: &str) -> (&str, Vec<(String, String)>) {
+    let (first, mut rest) = value.split_once(';').unwrap_or((value, ""));
+    let mut params = Vec::new();
+
+    while !rest.is_empty() {
+        let param = rest.trim_start_matches(|c: char| c == ';' || c.is_whitespace());
+        let key_end = param.find(['=', ';']).unwrap_or(param.len());
+        let key = param[..key_end].trim().to_ascii_lowercase();
+        rest = &param[key_end..];
+
+        let Some(after_eq) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let after_eq = after_eq.trim_start();
+        let value = match after_eq.strip_prefix('"') {
+            Some(quoted) => {
+                let mut value = String::new();
+                let mut chars = quoted.char_indices();
+                let mut end = quoted.len();
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                        '"' => {
+                            end = i + 1;
+                            break;
+                        }
+                        c => value.push(c),
+                    }
+                }
+                rest = &quoted[end..];
+                value
+            }
+            None => {
+                let end = after_eq.find(';').unwrap_or(after_eq.len());
+                rest = &after_eq[end..];
+                after_eq[..end].trim().to_string()
+            }
+        };
+        if !key.is_empty() {
+            params.push((key, value));
+        }
+    }
+
+    (first.trim(), params)
+}
+
+/// Split a multipart body into its parts
+///
+/// Returns None when the body does not contain the boundary at all.
+/// Parts without a `name` in their Content-Disposition are skipped.
+pub fn parse_multipart(body: &[u8], boundary: &str) -> Option<Vec<Part>> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let mut parts = Vec::new();
+
+    let mut pos = find(body, &delimiter, 0)? + delimiter.len();
+
+    loop {
+        // "--" right after a delimiter marks the end of the body
+        if body[pos..].starts_with(b"--") {
+            break;
+        }
+        pos = skip_line_break(body, pos);
+
+        let header_end = find(body, b"\r\n\r\n", pos)?;
+        let headers = String::from_utf8_lossy(&body[pos..header_end]);
+        let content_start = header_end + 4;
+
+        let mut next_delimiter = b"\r\n".to_vec();
+        next_delimiter.extend_from_slice(&delimiter);
+        let content_end = find(body, &next_delimiter, content_start)?;
+
+        if let Some(part) = parse_part(&headers, &body[content_start..content_end]) {
+            parts.push(part);
+        }
+
+        pos = content_end + next_delimiter.len();
+    }
+
+    Some(parts)
+}
+
+fn parse_part(headers: &str, data: &[u8]) -> Option<Part> {
+    let mut name = None;
+    let mut filename = None;
+    let mut content_type = None;
+
+    for line in headers.split("\r\n") {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        if key.trim().eq_ignore_ascii_case("Content-Disposition") {
+            for (key, value) in header_params(value).1 {
+                match key.as_str() {
+                    "name" => name = Some(value),
+                    "filename" => filename = Some(value),
+                    _ => {}
+                }
+            }
+        } else if key.trim().eq_ignore_ascii_case("Content-Type") {
+            content_type = Some(value.trim().to_string());
+        }
+    }
+
+    Some(Part {
+        name: name?,
+        filename,
+        content_type,
+        data: data.to_vec(),
+    })
+}
+
+fn skip_line_break(body: &[u8], pos: usize) -> usize {
+    if body[pos..].starts_with(b"\r\n") {
+        pos + 2
+    } else if body[pos..].starts_with(b"\n") {
+        pos + 1
+    } else {
+        pos
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    if from > haystack.len() {
+        return None;
+    }
+    haystack[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| i + from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &[u8] = b"--XyZ\r\n\
+Content-Disposition: form-data; name=\"comment\"\r\n\
+\r\n\
+hello\r\n\
+--XyZ\r\n\
+Content-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n\
+Content-Type: application/octet-stream\r\n\
+\r\n\
+\x00\x01\r\n\x02\r\n\
+--XyZ--\r\n";
+
+    #[test]
+    fn test_boundary() {
+        assert_eq!(boundary("multipart/form-data; boundary=XyZ"), Some("XyZ".to_string()));
+        assert_eq!(boundary("multipart/form-data; boundary=\"a b\""), Some("a b".to_string()));
+        assert_eq!(boundary("application/json"), None);
+    }
+
+    #[test]
+    fn test_parse_multipart() {
+        let parts = parse_multipart(BODY, "XyZ").unwrap();
+        assert_eq!(parts.len(), 2);
+
+        assert_eq!(parts[0].name, "comment");
+        assert!(!parts[0].is_file());
+        assert_eq!(parts[0].data, b"hello");
+
+        assert_eq!(parts[1].name, "upload");
+        assert_eq!(parts[1].filename.as_deref(), Some("a.bin"));
+        assert_eq!(parts[1].content_type.as_deref(), Some("application/octet-stream"));
+        assert_eq!(parts[1].data, b"\x00\x01\r\n\x02");
+    }
+
+    #[test]
+    fn test_quoted_parameters() {
+        let (disposition, params) =
+            header_params(r#" form-data; name="up;load"; filename="a;b \"c\".txt" ;size=3"#);
+        assert_eq!(disposition, "form-data");
+        assert_eq!(
+            params,
+            [
+                ("name".to_string(), "up;load".to_string()),
+                ("filename".to_string(), r#"a;b "c".txt"#.to_string()),
+                ("size".to_string(), "3".to_string()),
+            ]
+        );
+
+        let body = b"--XyZ\r\n\
+Content-Disposition: form-data; name=\"f\"; filename=\"a;b.txt\"\r\n\
+\r\n\
+x\r\n\
+--XyZ--\r\n";
+        let parts = parse_multipart(body, "XyZ").unwrap();
+        assert_eq!(parts[0].name, "f");
+        assert_eq!(parts[0].filename.as_deref(), Some("a;b.txt"));
+    }
+
+    #[test]
+    fn test_parse_multipart_wrong_boundary() {
+        assert!(parse_multipart(BODY, "other").is_none());
+    }
+}
@@ -1,28 +1,22 @@
-use crate::{Request, Response};
+use crate::{mount_prefix, Request, Response};
 
 /// Handles /redirect/{n} endpoint
 /// Redirects n times with absolute URLs
 pub fn redirect_handler(req: &Request) -> Response {
-    let n = crate::extract_param::<u32>(&req.path, r"/(?:h[123]/)?redirect/(\d+)")
-        .unwrap_or(1);
+    let n = match req.parse_param::<u32>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
+    let prefix = mount_prefix(&req.path, "/redirect/");
     
     if n <= 1 {
         // Final redirect - go to /get
         let mut response = Response::new(302);
-        response.headers.insert("Location".to_string(), "/get".to_string());
+        response.headers.insert("Location".to_string(), format!("{}/get", prefix));
         response
     } else {
         // Redirect to n-1
         let next_n = n - 1;
-        let prefix = if req.path.contains("/h1/") {
-            "/h1"
-        } else if req.path.contains("/h2/") {
-            "/h2"
-        } else if req.path.contains("/h3/") {
-            "/h3"
-        } else {
-            ""
-        };
         
         let mut response = Response::new(302);
         response.headers.insert(
@@ -36,26 +30,20 @@ pub fn redirect_handler(req: &Request) -> Response {
 /// Handles /relative-redirect/{n} endpoint
 /// Redirects n times with relative URLs
 pub fn relative_redirect_handler(req: &Request) -> Response {
-    let n = crate::extract_param::<u32>(&req.path, r"/(?:h[123]/)?relative-redirect/(\d+)")
-        .unwrap_or(1);
+    let n = match req.parse_param::<u32>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
+    let prefix = mount_prefix(&req.path, "/relative-redirect/");
     
     if n <= 1 {
         // Final redirect - go to /get
         let mut response = Response::new(302);
-        response.headers.insert("Location".to_string(), "/get".to_string());
+        response.headers.insert("Location".to_string(), format!("{}/get", prefix));
         response
     } else {
         // Redirect to n-1
         let next_n = n - 1;
-        let prefix = if req.path.contains("/h1/") {
-            "/h1"
-        } else if req.path.contains("/h2/") {
-            "/h2"
-        } else if req.path.contains("/h3/") {
-            "/h3"
-        } else {
-            ""
-        };
         
         let mut response = Response::new(302);
         response.headers.insert(
@@ -75,11 +63,12 @@ pub fn absolute_redirect_handler(req: &Request) -> Response {
 /// Handles /redirect-to endpoint
 /// Redirects to URL specified in query parameter
 pub fn redirect_to_handler(req: &Request) -> Response {
-    let query = crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""));
+    let query = req.args();
     
-    let url = query.get("url")
-        .cloned()
-        .unwrap_or_else(|| "/get".to_string());
+    let url = match query.get("url") {
+        Some(url) => url.to_string(),
+        None => format!("{}/get", mount_prefix(&req.path, "/redirect-to")),
+    };
     
     let status_code = query.get("status_code")
         .and_then(|s| s.parse::<u16>().ok())
@@ -93,47 +82,56 @@ pub fn redirect_to_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_redirect_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/redirect/3".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/redirect/3").with_param("n", "3");
         
         let response = redirect_handler(&req);
         assert_eq!(response.status, 302);
-        assert_eq!(response.headers.get("Location"), Some(&"/redirect/2".to_string()));
+        assert_eq!(response.headers.get("Location"), Some("/redirect/2"));
     }
     
+    #[test]
+    fn test_redirect_handler_keeps_mount_prefix() {
+        let req = Request::new("GET", "/h2h3/relative-redirect/3").with_param("n", "3");
+
+        let response = relative_redirect_handler(&req);
+        assert_eq!(response.headers.get("Location"), Some("/h2h3/relative-redirect/2"));
+    }
+
+    #[test]
+    fn test_redirect_handler_final_keeps_mount_prefix() {
+        let req = Request::new("GET", "/h2h3/redirect/1").with_param("n", "1");
+
+        let response = redirect_handler(&req);
+        assert_eq!(response.headers.get("Location"), Some("/h2h3/get"));
+    }
+
     #[test]
     fn test_redirect_handler_final() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/redirect/1".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/redirect/1").with_param("n", "1");
         
         let response = redirect_handler(&req);
         assert_eq!(response.status, 302);
-        assert_eq!(response.headers.get("Location"), Some(&"/get".to_string()));
+        assert_eq!(response.headers.get("Location"), Some("/get"));
     }
     
     #[test]
     fn test_redirect_to_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/redirect-to?url=https://example.com&status_code=301".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/redirect-to?url=https://example.com&status_code=301");
         
         let response = redirect_to_handler(&req);
         assert_eq!(response.status, 301);
-        assert_eq!(response.headers.get("Location"), Some(&"https://example.com".to_string()));
+        assert_eq!(response.headers.get("Location"), Some("https://example.com"));
+    }
+    
+    #[test]
+    fn test_redirect_to_handler_encoded_url() {
+        let req = Request::new("GET", "/redirect-to?url=%2Fget%3Fa%3D1");
+        
+        let response = redirect_to_handler(&req);
+        assert_eq!(response.status, 302);
+        assert_eq!(response.headers.get("Location"), Some("/get?a=1"));
     }
 }
@@ -135,16 +135,10 @@ pub fn encoding_utf8_handler(_req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_json_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/json".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/json");
         
         let response = json_handler(&req);
         assert_eq!(response.status, 200);
@@ -153,12 +147,7 @@ mod tests {
     
     #[test]
     fn test_html_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/html".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/html");
         
         let response = html_handler(&req);
         assert_eq!(response.status, 200);
@@ -167,12 +156,7 @@ mod tests {
     
     #[test]
     fn test_xml_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/xml".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/xml");
         
         let response = xml_handler(&req);
         assert_eq!(response.status, 200);
@@ -181,12 +165,7 @@ mod tests {
     
     #[test]
     fn test_robots_txt_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/robots.txt".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/robots.txt");
         
         let response = robots_txt_handler(&req);
         assert_eq!(response.status, 200);
@@ -0,0 +1,34 @@
+//! Endpoint limits and reported values that a deployment can change
+//!
+//! The defaults match httpbin.org. Handlers read them from
+//! `Request::settings`.
+
+/// Caps and fixed values used by the handlers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointSettings {
+    /// Scheme and authority reported in `url` fields, without a trailing slash
+    pub base_url: String,
+    /// Realm of Basic and Digest auth challenges
+    pub realm: String,
+    /// Largest body /bytes, /stream-bytes, /range and /drip produce
+    pub max_bytes: usize,
+    /// Longest /delay, and longest /drip duration and initial delay, in seconds
+    pub max_delay: u64,
+    /// Most lines /stream sends
+    pub max_stream_lines: usize,
+    /// Most links /links generates
+    pub max_links: usize,
+}
+
+impl Default for EndpointSettings {
+    fn default() -> Self {
+        Self {
+            base_url: "https://httpbin.org".to_string(),
+            realm: "Fake Realm".to_string(),
+            max_bytes: 102400,
+            max_delay: 10,
+            max_stream_lines: 100,
+            max_links: 200,
+        }
+    }
+}
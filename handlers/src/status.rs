@@ -1,5 +1,4 @@
 use crate::{Request, Response};
-use regex::Regex;
 
 /// Handles /status/{code} endpoint
 /// Returns the specified HTTP status code
@@ -8,14 +7,10 @@ use regex::Regex;
 /// - /status/200 -> Returns 200 OK
 /// - /h2/status/404 -> Returns 404 Not Found
 pub fn status_handler(req: &Request) -> Response {
-    // Extract status code from path
-    // Matches: /status/200, /h1/status/404, /h2/status/500, etc.
-    let re = Regex::new(r"/(?:h[123]/)?status/(\d+)").unwrap();
-    
-    let code = re.captures(&req.path)
-        .and_then(|caps| caps.get(1))
-        .and_then(|m| m.as_str().parse::<u16>().ok())
-        .unwrap_or(200);
+    let code = match req.parse_param::<u16>("code") {
+        Ok(code) => code,
+        Err(response) => return response,
+    };
     
     // Validate status code is in valid range (100-599)
     let code = if (100..=599).contains(&code) {
@@ -31,16 +26,11 @@ pub fn status_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::HttpVersion;
     
     #[test]
     fn test_status_200() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/status/200".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/status/200").with_param("code", "200");
         
         let response = status_handler(&req);
         assert_eq!(response.status, 200);
@@ -48,12 +38,7 @@ mod tests {
     
     #[test]
     fn test_status_404() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/status/404".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/status/404").with_param("code", "404");
         
         let response = status_handler(&req);
         assert_eq!(response.status, 404);
@@ -62,10 +47,8 @@ mod tests {
     #[test]
     fn test_status_with_h2_prefix() {
         let req = Request {
-            method: "GET".to_string(),
-            path: "/h2/status/200".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/2".to_string(),
+            version: HttpVersion::Http2,
+            ..Request::new("GET", "/h2/status/200").with_param("code", "200")
         };
         
         let response = status_handler(&req);
@@ -74,14 +57,18 @@ mod tests {
     
     #[test]
     fn test_invalid_status_code() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/status/999".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/status/999").with_param("code", "999");
         
         let response = status_handler(&req);
         assert_eq!(response.status, 400);
     }
+
+    #[test]
+    fn test_status_malformed_code() {
+        let req = Request::new("GET", "/status/abc").with_param("code", "abc");
+
+        let response = status_handler(&req);
+        assert_eq!(response.status, 400);
+        assert_eq!(response.body, b"Invalid code: 'abc'");
+    }
 }
@@ -1,14 +1,17 @@
-use crate::{Request, Response};
+use crate::{BodyStream, Request, Response};
 use serde_json::json;
+use std::time::Duration;
 
 /// Handles /bytes/{n} endpoint
 /// Generates n random bytes of binary data
 pub fn bytes_handler(req: &Request) -> Response {
-    let n = crate::extract_param::<usize>(&req.path, r"/(?:h[123]/)?bytes/(\d+)")
-        .unwrap_or(1024);
+    let n = match req.parse_param::<usize>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
     
-    // Cap at 100KB to prevent abuse
-    let n = n.min(102400);
+    // Cap to prevent abuse (100KB by default)
+    let n = n.min(req.settings.max_bytes);
     
     let data = crate::random_bytes(n);
     
@@ -19,63 +22,94 @@ pub fn bytes_handler(req: &Request) -> Response {
 }
 
 /// Handles /stream-bytes/{n} endpoint
-/// Streams n random bytes in chunked encoding
+/// Streams n random bytes in chunks of `chunk_size` (default 10240)
 pub fn stream_bytes_handler(req: &Request) -> Response {
-    // For now, same as bytes_handler
-    // In a real implementation, this would use chunked transfer encoding
-    bytes_handler(req)
+    let n = match req.parse_param::<usize>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
+
+    // Cap to prevent abuse (100KB by default)
+    let n = n.min(req.settings.max_bytes);
+
+    let chunk_size = req.args()
+        .get("chunk_size")
+        .and_then(|s| s.parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(10240);
+
+    let data = crate::random_bytes(n);
+
+    let mut stream = BodyStream::new();
+    for chunk in data.chunks(chunk_size) {
+        stream.push(chunk);
+    }
+
+    let mut response = Response::new(200).with_stream(stream);
+    response.headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
+    response
 }
 
 /// Handles /stream/{n} endpoint
-/// Streams n JSON responses
+/// Streams n JSON lines, one chunk per line
 pub fn stream_handler(req: &Request) -> Response {
-    let n = crate::extract_param::<usize>(&req.path, r"/(?:h[123]/)?stream/(\d+)")
-        .unwrap_or(10);
+    let n = match req.parse_param::<usize>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
     
-    // Cap at 100 items
-    let n = n.min(100);
+    // Cap at 100 items by default
+    let n = n.min(req.settings.max_stream_lines);
     
-    let mut lines = Vec::new();
+    let mut stream = BodyStream::new();
     for i in 0..n {
         let item = json!({
             "id": i,
-            "url": format!("https://httpbin.org{}", req.path),
+            "url": req.url(),
             "headers": req.headers
         });
-        lines.push(serde_json::to_string(&item).unwrap());
+        stream.push(format!("{}\n", item));
     }
     
-    let body = lines.join("\n");
-    
-    let mut response = Response::new(200);
-    response.body = body.into_bytes();
+    let mut response = Response::new(200).with_stream(stream);
     response.headers.insert("Content-Type".to_string(), "application/json".to_string());
     response
 }
 
 /// Handles /drip endpoint
-/// Drips data over duration with optional delay
+/// Drips `numbytes` bytes evenly over `duration` seconds: the first one
+/// after `delay` seconds, like httpbin, the rest `duration / numbytes` apart.
+/// The body is built up front, one chunk per byte; only the sending is paced.
 pub fn drip_handler(req: &Request) -> Response {
-    let query = crate::parse_query(&req.path.split('?').nth(1).unwrap_or(""));
+    let query = req.args();
     
-    let _duration = query.get("duration")
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(2);
+    let duration = query.get("duration")
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|d| d.is_finite() && *d >= 0.0)
+        .unwrap_or(2.0)
+        .min(req.settings.max_delay as f64);
     
     let numbytes = query.get("numbytes")
         .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(10);
+        .unwrap_or(10)
+        .min(req.settings.max_bytes);
     
-    let _delay = query.get("delay")
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(0);
+    let delay = query.get("delay")
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|d| d.is_finite() && *d >= 0.0)
+        .unwrap_or(0.0)
+        .min(req.settings.max_delay as f64);
     
-    // For now, just return the data at once
-    // In a real implementation, this would drip data over time
-    let data = crate::random_bytes(numbytes);
+    let mut stream = BodyStream::new();
+    if numbytes > 0 {
+        let pause = Duration::from_secs_f64(duration / numbytes as f64);
+        for i in 0..numbytes {
+            let wait = if i == 0 { Duration::from_secs_f64(delay) } else { pause };
+            stream.push_after(wait, b"*".to_vec());
+        }
+    }
     
-    let mut response = Response::new(200);
-    response.body = data;
+    let mut response = Response::new(200).with_stream(stream);
     response.headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
     response
 }
@@ -83,71 +117,105 @@ pub fn drip_handler(req: &Request) -> Response {
 /// Handles /range/{n} endpoint
 /// Streams n bytes with Range header support
 pub fn range_handler(req: &Request) -> Response {
-    let n = crate::extract_param::<usize>(&req.path, r"/(?:h[123]/)?range/(\d+)")
-        .unwrap_or(1024);
-    
-    let n = n.min(102400);
-    
-    // Check for Range header
-    let range_header = req.headers.get("Range")
-        .or_else(|| req.headers.get("range"));
+    let n = match req.parse_param::<usize>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
     
+    let n = n.min(req.settings.max_bytes);
     let data = crate::random_bytes(n);
-    
-    if let Some(range) = range_header {
-        // Parse Range header (simplified)
-        if let Some(range_str) = range.strip_prefix("bytes=") {
-            if let Some((start_str, end_str)) = range_str.split_once('-') {
-                let start = start_str.parse::<usize>().unwrap_or(0);
-                let end = if end_str.is_empty() {
-                    n - 1
-                } else {
-                    end_str.parse::<usize>().unwrap_or(n - 1).min(n - 1)
-                };
-                
-                if start < data.len() && start <= end {
-                    let range_data = data[start..=end.min(data.len() - 1)].to_vec();
-                    let mut response = Response::new(206); // Partial Content
-                    response.body = range_data;
-                    response.headers.insert(
-                        "Content-Range".to_string(),
-                        format!("bytes {}-{}/{}", start, end.min(data.len() - 1), n)
-                    );
-                    response.headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
-                    return response;
-                }
+
+    let range = req.headers.get("Range").and_then(parse_byte_range);
+    let mut response = match range {
+        Some(range) => match range.resolve(n) {
+            Some((first, last)) => {
+                let mut response = Response::new(206); // Partial Content
+                response.body = data[first..=last].to_vec();
+                response.headers.insert("Content-Range", format!("bytes {}-{}/{}", first, last, n));
+                response
+            }
+            None => {
+                let mut response = Response::new(416); // Range Not Satisfiable
+                response.headers.insert("Content-Range", format!("bytes */{}", n));
+                return response;
             }
+        },
+        // No range, or one we do not understand: the full content
+        None => {
+            let mut response = Response::new(200);
+            response.body = data;
+            response
         }
-    }
-    
-    // No range or invalid range - return full content
-    let mut response = Response::new(200);
-    response.body = data;
+    };
     response.headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
     response.headers.insert("Accept-Ranges".to_string(), "bytes".to_string());
     response
 }
 
+/// A single `bytes=` range, before it is checked against the content length
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// `first-last`, or `first-` with no last
+    From(usize, Option<usize>),
+    /// `-length`: the last `length` bytes
+    Suffix(usize),
+}
+
+impl ByteRange {
+    /// First and last byte offsets within `len` bytes, or None when the
+    /// range is unsatisfiable
+    fn resolve(&self, len: usize) -> Option<(usize, usize)> {
+        match *self {
+            ByteRange::From(first, last) if first < len => {
+                Some((first, last.map_or(len - 1, |last| last.min(len - 1))))
+            }
+            ByteRange::Suffix(length) if length > 0 && len > 0 => Some((len - length.min(len), len - 1)),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a `Range` header holding one byte range
+///
+/// Anything else (other units, several ranges, bad syntax) is None, and the
+/// header is ignored as RFC 9110 allows.
+fn parse_byte_range(header: &str) -> Option<ByteRange> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    let (first, last) = spec.trim().split_once('-')?;
+    let number = |s: &str| s.parse::<usize>().ok();
+
+    match (first.is_empty(), last.is_empty()) {
+        (true, false) => number(last).map(ByteRange::Suffix),
+        (false, true) => number(first).map(|first| ByteRange::From(first, None)),
+        (false, false) => {
+            let (first, last) = (number(first)?, number(last)?);
+            (first <= last).then_some(ByteRange::From(first, Some(last)))
+        }
+        (true, true) => None,
+    }
+}
+
 /// Handles /links/{n}/{offset} endpoint
 /// Generates page with n links
 pub fn links_handler(req: &Request) -> Response {
-    let params = crate::extract_params(&req.path, r"/(?:h[123]/)?links/(\d+)(?:/(\d+))?")
-        .unwrap_or_default();
-    
-    let n = params.get(0)
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(10)
-        .min(200); // Cap at 200 links
-    
-    let offset = params.get(1)
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(0);
+    let n = match req.parse_param::<usize>("n") {
+        Ok(n) => n,
+        Err(response) => return response,
+    };
+    let offset = match req.parse_param::<usize>("offset") {
+        Ok(offset) => offset,
+        Err(response) => return response,
+    };
+
+    // Cap at 200 links by default
+    let n = n.min(req.settings.max_links);
+    let prefix = crate::mount_prefix(&req.path, "/links/");
     
     let mut html = String::from("<!DOCTYPE html>\n<html>\n<head><title>Links</title></head>\n<body>\n");
     
     for i in 0..n {
         let link_num = i + offset;
-        html.push_str(&format!("<a href=\"/links/{}/{}\">Link {}</a><br>\n", n, link_num, link_num));
+        html.push_str(&format!("<a href=\"{}/links/{}/{}\">Link {}</a><br>\n", prefix, n, link_num, link_num));
     }
     
     html.push_str("</body>\n</html>");
@@ -161,16 +229,10 @@ pub fn links_handler(req: &Request) -> Response {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     
     #[test]
     fn test_bytes_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/bytes/100".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/bytes/100").with_param("n", "100");
         
         let response = bytes_handler(&req);
         assert_eq!(response.status, 200);
@@ -178,31 +240,89 @@ mod tests {
     }
     
     #[test]
-    fn test_stream_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/stream/5".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
+    fn test_malformed_count() {
+        let req = Request::new("GET", "/bytes/x").with_param("n", "x");
+        assert_eq!(bytes_handler(&req).status, 400);
+
+        let req = Request::new("GET", "/links/5/-1")
+            .with_param("n", "5")
+            .with_param("offset", "-1");
+        assert_eq!(links_handler(&req).status, 400);
+    }
+
+    #[test]
+    fn test_configured_caps() {
+        let settings = std::sync::Arc::new(crate::EndpointSettings {
+            max_bytes: 50,
+            max_delay: 1,
+            max_links: 3,
+            ..Default::default()
+        });
+        let with_settings = |req: Request| Request {
+            settings: settings.clone(),
+            ..req
         };
+
+        let bytes = with_settings(Request::new("GET", "/bytes/100").with_param("n", "100"));
+        assert_eq!(bytes_handler(&bytes).body.len(), 50);
+        let range = with_settings(Request::new("GET", "/range/100").with_param("n", "100"));
+        assert_eq!(range_handler(&range).body.len(), 50);
+
+        let drip = drip_handler(&with_settings(Request::new("GET", "/drip?numbytes=100&duration=5")));
+        let stream = drip.stream.unwrap();
+        assert_eq!(stream.len(), 50);
+        assert_eq!(stream.duration(), Duration::from_millis(980));
+
+        let links = Request::new("GET", "/links/10/0")
+            .with_param("n", "10")
+            .with_param("offset", "0");
+        let links = links_handler(&with_settings(links));
+        assert_eq!(String::from_utf8(links.body).unwrap().matches("<a href").count(), 3);
+    }
+
+    #[test]
+    fn test_stream_handler() {
+        let req = Request::new("GET", "/stream/5").with_param("n", "5");
         
         let response = stream_handler(&req);
         assert_eq!(response.status, 200);
-        let body = String::from_utf8(response.body).unwrap();
+        let stream = response.stream.as_ref().unwrap();
+        assert_eq!(stream.chunks().len(), 5);
+        let body = String::from_utf8(response.full_body()).unwrap();
         assert_eq!(body.lines().count(), 5);
     }
+
+    #[test]
+    fn test_stream_bytes_chunk_size() {
+        let req = Request::new("GET", "/stream-bytes/1000?chunk_size=300").with_param("n", "1000");
+
+        let response = stream_bytes_handler(&req);
+        let stream = response.stream.unwrap();
+        let sizes: Vec<usize> = stream.chunks().iter().map(|c| c.data.len()).collect();
+        assert_eq!(sizes, vec![300, 300, 300, 100]);
+    }
+
+    #[test]
+    fn test_drip_handler_paces_bytes() {
+        let req = Request::new("GET", "/drip?numbytes=4&duration=2&delay=1");
+
+        let response = drip_handler(&req);
+        let stream = response.stream.unwrap();
+        assert_eq!(stream.to_vec(), b"****");
+        assert_eq!(stream.chunks()[0].delay, Duration::from_secs(1));
+        assert_eq!(stream.chunks()[1].delay, Duration::from_millis(500));
+        assert_eq!(stream.duration(), Duration::from_millis(2500));
+
+        let req = Request::new("GET", "/drip?numbytes=2&duration=2");
+        let stream = drip_handler(&req).stream.unwrap();
+        assert_eq!(stream.chunks()[0].delay, Duration::ZERO);
+    }
     
     #[test]
     fn test_range_handler() {
-        let mut headers = HashMap::new();
-        headers.insert("Range".to_string(), "bytes=0-99".to_string());
-        
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/range/1024".to_string(),
-            headers,
-            http_version: "HTTP/1.1".to_string(),
-        };
+        let req = Request::new("GET", "/range/1024")
+            .with_param("n", "1024")
+            .with_header("Range", "bytes=0-99");
         
         let response = range_handler(&req);
         assert_eq!(response.status, 206);
@@ -210,17 +330,47 @@ mod tests {
     }
     
     #[test]
-    fn test_links_handler() {
-        let req = Request {
-            method: "GET".to_string(),
-            path: "/links/5/0".to_string(),
-            headers: HashMap::new(),
-            http_version: "HTTP/1.1".to_string(),
+    fn test_range_edge_cases() {
+        let range = |n: &str, header: &str| {
+            let req = Request::new("GET", "/range").with_param("n", n).with_header("Range", header);
+            range_handler(&req)
         };
+
+        let response = range("0", "bytes=0-");
+        assert_eq!(response.status, 416);
+        assert_eq!(response.headers.get("Content-Range"), Some("bytes */0"));
+        assert_eq!(range("100", "bytes=100-").status, 416);
+        assert_eq!(range("100", "bytes=-0").status, 416);
+
+        let response = range("100", "bytes=-10");
+        assert_eq!(response.status, 206);
+        assert_eq!(response.headers.get("Content-Range"), Some("bytes 90-99/100"));
+        let response = range("100", "bytes=95-500");
+        assert_eq!(response.headers.get("Content-Range"), Some("bytes 95-99/100"));
+
+        // Unparsable ranges are ignored
+        assert_eq!(range("100", "bytes=9-3").status, 200);
+        assert_eq!(range("100", "items=0-1").status, 200);
+        let empty = Request::new("GET", "/range/0").with_param("n", "0");
+        assert_eq!(range_handler(&empty).status, 200);
+    }
+
+    #[test]
+    fn test_links_handler() {
+        let req = Request::new("GET", "/links/5/0")
+            .with_param("n", "5")
+            .with_param("offset", "0");
         
         let response = links_handler(&req);
         assert_eq!(response.status, 200);
         let body = String::from_utf8(response.body).unwrap();
-        assert!(body.contains("<a href"));
+        assert!(body.contains("<a href=\"/links/5/3\">Link 3</a>"));
+
+        let req = Request::new("GET", "/h2h3/links/2/0")
+            .with_param("n", "2")
+            .with_param("offset", "0");
+        let body = String::from_utf8(links_handler(&req).body).unwrap();
+        assert!(body.contains("<a href=\"/h2h3/links/2/1\">Link 1</a>"));
+        assert!(!body.contains("href=\"/links/"));
     }
 }
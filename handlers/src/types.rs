@@ -1,37 +1,324 @@
-use serde::{Deserialize, Serialize};
+use crate::{ConnectionInfo, EndpointSettings, Headers, HttpVersion};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Standard httpbin response format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HttpBinResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub args: Option<HashMap<String, String>>,
-    
+    pub args: Option<HashMap<String, serde_json::Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<HashMap<String, serde_json::Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form: Option<HashMap<String, serde_json::Value>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<HashMap<String, String>>,
-    
+    pub headers: Option<Headers>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
-    
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub origin: Option<String>,
 }
 
+/// Ordered list of decoded key/value pairs (query args, form fields)
+///
+/// Serializes httpbin-style: a plain string when a key appears once,
+/// a list of strings when it repeats.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Append a value, keeping any earlier values for the same key
+    pub fn push(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.push((key.into(), value.into()));
+    }
+
+    /// First value for a key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// All values for a key, in order of appearance
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0.iter().filter(move |(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.iter().any(|(k, _)| k == key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Distinct keys, in order of first appearance
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = Vec::new();
+        for (k, _) in &self.0 {
+            if !keys.contains(&k.as_str()) {
+                keys.push(k);
+            }
+        }
+        keys
+    }
+
+    /// httpbin representation as a JSON-compatible map
+    pub fn to_json_map(&self) -> HashMap<String, serde_json::Value> {
+        self.keys()
+            .into_iter()
+            .map(|key| (key.to_string(), self.json_value(key)))
+            .collect()
+    }
+
+    fn json_value(&self, key: &str) -> serde_json::Value {
+        let values: Vec<&str> = self.get_all(key).collect();
+        match values.as_slice() {
+            [single] => serde_json::Value::String(single.to_string()),
+            _ => serde_json::Value::from(values),
+        }
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Serialize for Params {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let keys = self.keys();
+        let mut map = serializer.serialize_map(Some(keys.len()))?;
+        for key in keys {
+            map.serialize_entry(key, &self.json_value(key))?;
+        }
+        map.end()
+    }
+}
+
 /// Represents an HTTP request
 #[derive(Debug, Clone)]
 pub struct Request {
     pub method: String,
+    /// Request path, without the query string
     pub path: String,
-    pub headers: HashMap<String, String>,
-    pub http_version: String,
+    /// Raw query string, without the leading `?` (empty if none)
+    pub query: String,
+    pub headers: Headers,
+    pub version: HttpVersion,
+    pub body: Vec<u8>,
+    pub connection: ConnectionInfo,
+    /// Originating client address, after trusted-proxy headers are applied
+    pub client_ip: Option<IpAddr>,
+    /// Decoded `{param}` values captured by the router
+    pub path_params: Params,
+    /// Limits and reported values configured for the server
+    pub settings: Arc<EndpointSettings>,
+}
+
+impl Request {
+    /// Create a new HTTP/1.1 request with no headers or body
+    ///
+    /// `target` is split into path and query at the first `?`.
+    pub fn new(method: &str, target: &str) -> Self {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query.to_string(),
+            headers: Headers::new(),
+            version: HttpVersion::Http11,
+            body: Vec::new(),
+            connection: ConnectionInfo::default(),
+            client_ip: None,
+            path_params: Params::new(),
+            settings: Arc::default(),
+        }
+    }
+
+    /// Add a header, keeping earlier values with the same name
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set the request body
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Add a path parameter, as the router does for `{name}` segments
+    pub fn with_param(mut self, name: &str, value: &str) -> Self {
+        self.path_params.push(name, value);
+        self
+    }
+
+    /// A path parameter captured by the router
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.path_params.get(name)
+    }
+
+    /// A path parameter parsed as `T`, or a 400 response saying which one
+    /// is missing or malformed
+    pub fn parse_param<T: FromStr>(&self, name: &str) -> Result<T, Response> {
+        match self.param(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| Response::new(400).with_text(&format!("Invalid {}: '{}'", name, value))),
+            None => Err(Response::new(400).with_text(&format!("Missing {}", name))),
+        }
+    }
+
+    /// Decoded query arguments
+    pub fn args(&self) -> Params {
+        crate::parse_query(&self.query)
+    }
+
+    /// Path plus query string, as sent on the request line
+    pub fn uri(&self) -> String {
+        if self.query.is_empty() {
+            self.path.clone()
+        } else {
+            format!("{}?{}", self.path, self.query)
+        }
+    }
+
+    /// Absolute URL reported back in httpbin's `url` field
+    pub fn url(&self) -> String {
+        format!("{}{}", self.settings.base_url, self.uri())
+    }
+
+    /// Client address reported in httpbin's `origin` field
+    pub fn origin(&self) -> Option<String> {
+        self.client_ip.map(|ip| ip.to_string())
+    }
+
+    /// Get the Content-Type header, if present
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers.get("Content-Type")
+    }
+}
+
+/// One piece of a streamed body, sent after `delay`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    pub delay: Duration,
+    pub data: Vec<u8>,
+}
+
+/// Response body sent incrementally
+///
+/// Each chunk is flushed on its own: as a chunk of a chunked HTTP/1.1 body,
+/// or as a DATA frame on HTTP/2 and HTTP/3.
+///
+/// The chunks are all held in memory before the response starts; only their
+/// sending is paced. That is why the streaming endpoints cap their sizes
+/// (`EndpointSettings::max_bytes`, `max_stream_lines`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BodyStream {
+    chunks: Vec<Chunk>,
+}
+
+impl BodyStream {
+    pub fn new() -> Self {
+        Self { chunks: Vec::new() }
+    }
+
+    /// Append a chunk sent right after the previous one
+    pub fn push(&mut self, data: impl Into<Vec<u8>>) {
+        self.push_after(Duration::ZERO, data);
+    }
+
+    /// Append a chunk sent `delay` after the previous one
+    pub fn push_after(&mut self, delay: Duration, data: impl Into<Vec<u8>>) {
+        self.chunks.push(Chunk {
+            delay,
+            data: data.into(),
+        });
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    /// Total body length in bytes
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.data.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Time from the first to the last byte being sent
+    pub fn duration(&self) -> Duration {
+        self.chunks.iter().map(|chunk| chunk.delay).sum()
+    }
+
+    /// All chunks concatenated
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.chunks.iter().flat_map(|chunk| chunk.data.iter().copied()).collect()
+    }
+}
+
+/// A way to fail a request instead of sending a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abort {
+    /// Ask the client to retry over HTTP/1.1 (HTTP_1_1_REQUIRED on HTTP/2,
+    /// H3_VERSION_FALLBACK on HTTP/3, a connection close on HTTP/1.x)
+    Http11Required,
+    /// Close the connection without a response
+    CloseConnection,
+}
+
+impl Abort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Abort::Http11Required => "http11-required",
+            Abort::CloseConnection => "close",
+        }
+    }
 }
 
 /// Represents an HTTP response
 #[derive(Debug, Clone)]
 pub struct Response {
     pub status: u16,
-    pub headers: HashMap<String, String>,
+    pub headers: Headers,
     pub body: Vec<u8>,
+    /// Streamed body, sent instead of `body` when set
+    pub stream: Option<BodyStream>,
+    /// Fail the request this way instead of sending the response
+    pub abort: Option<Abort>,
 }
 
 impl Response {
@@ -39,11 +326,36 @@ impl Response {
     pub fn new(status: u16) -> Self {
         Self {
             status,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: Vec::new(),
+            stream: None,
+            abort: None,
+        }
+    }
+
+    /// A response that fails the request instead of being sent
+    pub fn aborted(abort: Abort) -> Self {
+        Self {
+            abort: Some(abort),
+            ..Self::new(500)
+        }
+    }
+
+    /// Send the body incrementally instead of all at once
+    pub fn with_stream(mut self, stream: BodyStream) -> Self {
+        self.body.clear();
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Body bytes as the client receives them, streamed or not
+    pub fn full_body(&self) -> Vec<u8> {
+        match &self.stream {
+            Some(stream) => stream.to_vec(),
+            None => self.body.clone(),
         }
     }
-    
+
     /// Set JSON body
     pub fn with_json(mut self, value: &impl Serialize) -> Self {
         self.body = serde_json::to_vec(value).unwrap_or_default();
@@ -53,7 +365,7 @@ impl Response {
         );
         self
     }
-    
+
     /// Set plain text body
     pub fn with_text(mut self, text: &str) -> Self {
         self.body = text.as_bytes().to_vec();
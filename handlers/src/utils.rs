@@ -1,60 +1,66 @@
-use regex::Regex;
-use std::collections::HashMap;
-use std::str::FromStr;
-
-/// Extract a path parameter using regex
+/// Parse query string into Params
 /// 
-/// Example: extract_param::<u16>("/status/200", r"/status/(\d+)") -> Some(200)
-pub fn extract_param<T: FromStr>(path: &str, pattern: &str) -> Option<T> {
-    let re = Regex::new(pattern).ok()?;
-    re.captures(path)
-        .and_then(|caps| caps.get(1))
-        .and_then(|m| m.as_str().parse().ok())
+/// Keys and values are percent-decoded and repeated keys are kept.
+/// Example: parse_query("foo=bar&foo=baz") -> {"foo": ["bar", "baz"]}
+pub fn parse_query(query: &str) -> crate::Params {
+    parse_urlencoded(query)
 }
 
-/// Extract multiple path parameters
-pub fn extract_params(path: &str, pattern: &str) -> Option<Vec<String>> {
-    let re = Regex::new(pattern).ok()?;
-    let caps = re.captures(path)?;
-    
-    let mut params = Vec::new();
-    for i in 1..caps.len() {
-        if let Some(m) = caps.get(i) {
-            params.push(m.as_str().to_string());
+/// Parse an application/x-www-form-urlencoded string into Params
+///
+/// Keys and values are percent-decoded and `+` is treated as a space.
+/// Repeated keys are kept in order.
+pub fn parse_urlencoded(input: &str) -> crate::Params {
+    input
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_form_component(key), decode_form_component(value))
+        })
+        .collect()
+}
+
+/// Percent-decode a string, leaving malformed escapes untouched
+///
+/// Example: percent_decode("a%20b") -> "a b"
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                decoded.push(hi << 4 | lo);
+                i += 3;
+                continue;
+            }
         }
+        decoded.push(bytes[i]);
+        i += 1;
     }
-    
-    Some(params)
+
+    String::from_utf8_lossy(&decoded).into_owned()
 }
 
-/// Parse query string into HashMap
-/// 
-/// Example: parse_query("foo=bar&baz=qux") -> {"foo": "bar", "baz": "qux"}
-pub fn parse_query(query: &str) -> HashMap<String, String> {
-    if query.is_empty() {
-        return HashMap::new();
+fn decode_form_component(input: &str) -> String {
+    percent_decode(&input.replace('+', " "))
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
     }
-    
-    query
-        .split('&')
-        .filter_map(|pair| {
-            let mut parts = pair.splitn(2, '=');
-            let key = parts.next()?;
-            let value = parts.next().unwrap_or("");
-            Some((key.to_string(), value.to_string()))
-        })
-        .collect()
 }
 
-/// Get client IP from request headers
-/// Checks X-Forwarded-For first, then X-Real-IP
-pub fn get_client_ip(headers: &HashMap<String, String>) -> Option<String> {
-    headers
-        .get("X-Forwarded-For")
-        .or_else(|| headers.get("x-forwarded-for"))
-        .or_else(|| headers.get("X-Real-IP"))
-        .or_else(|| headers.get("x-real-ip"))
-        .map(|s| s.split(',').next().unwrap_or(s).trim().to_string())
+/// The mount prefix in front of `endpoint` (e.g. "/h2h3"), so redirects and
+/// links stay under the same protocol constraint
+pub fn mount_prefix<'a>(path: &'a str, endpoint: &str) -> &'a str {
+    path.find(endpoint).map_or("", |i| &path[..i])
 }
 
 /// Generate random bytes
@@ -80,34 +86,32 @@ pub fn encode_base64(data: &[u8]) -> String {
 mod tests {
     use super::*;
     
-    #[test]
-    fn test_extract_param() {
-        let result: Option<u16> = extract_param("/status/200", r"/status/(\d+)");
-        assert_eq!(result, Some(200));
-        
-        let result: Option<u16> = extract_param("/status/abc", r"/status/(\d+)");
-        assert_eq!(result, None);
-    }
-    
     #[test]
     fn test_parse_query() {
         let result = parse_query("foo=bar&baz=qux");
-        assert_eq!(result.get("foo"), Some(&"bar".to_string()));
-        assert_eq!(result.get("baz"), Some(&"qux".to_string()));
+        assert_eq!(result.get("foo"), Some("bar"));
+        assert_eq!(result.get("baz"), Some("qux"));
+        
+        let result = parse_query("q=a%26b&q=c+d");
+        assert_eq!(result.get_all("q").collect::<Vec<_>>(), vec!["a&b", "c d"]);
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({"q": ["a&b", "c d"]})
+        );
         
         let result = parse_query("");
         assert!(result.is_empty());
     }
     
     #[test]
-    fn test_get_client_ip() {
-        let mut headers = HashMap::new();
-        headers.insert("X-Forwarded-For".to_string(), "192.168.1.1".to_string());
-        
-        assert_eq!(get_client_ip(&headers), Some("192.168.1.1".to_string()));
-        
-        headers.clear();
-        assert_eq!(get_client_ip(&headers), None);
+    fn test_parse_urlencoded() {
+        let result = parse_urlencoded("name=J%C3%BCrgen+M&tag=a&tag=b&empty");
+        assert_eq!(result.get("name"), Some("Jürgen M"));
+        assert_eq!(result.get_all("tag").collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(result.get("empty"), Some(""));
+
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%41"), "%zzA");
     }
     
     #[test]
@@ -0,0 +1,401 @@
+//! Per-request access log, as JSON lines or Common Log Format
+//!
+//! One line is written when the response is handed to the connection, so a
+//! paced body (e.g. /drip) may still be going out after its line appears;
+//! `duration_ms` covers producing the response, not sending it.
+//!
+//! A Common Log Format line carries the standard fields followed by the
+//! duration in milliseconds and the route's protocol constraint:
+//!
+//! ```text
+//! 127.0.0.1 - - [17/Oct/2026:09:30:00 +0000] "GET /h2/get?a=1 HTTP/2" 200 312 4 h2
+//! ```
+//!
+//! A request failed without a response (a protocol mismatch in `close` or
+//! `http11-required` mode) has no status: JSON gives `"status":null` and the
+//! abort kind in `abort`, and CLF writes status `000` with the abort kind
+//! appended:
+//!
+//! ```text
+//! 127.0.0.1 - - [17/Oct/2026:09:30:00 +0000] "GET /h1/get HTTP/2" 000 0 0 h1 close
+//! ```
+
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{self, LineWriter, Write};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use httpbin_handlers::{Abort, HttpVersion, Request, Response};
+use serde::Serialize;
+
+use crate::protocol::ProtocolSet;
+
+/// Line format of the access log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessLogFormat {
+    /// One JSON object per request
+    Json,
+    /// Common Log Format, plus duration and protocol constraint
+    Common,
+}
+
+/// The route a request matched, as far as the access log is concerned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch {
+    /// Registered pattern, including the mount prefix, e.g. `/h2/status/{code}`
+    pub pattern: String,
+    /// Protocols the route accepts; None accepts every protocol
+    pub protocols: Option<ProtocolSet>,
+}
+
+impl RouteMatch {
+    /// Protocol constraint as written in a mount, or "any"
+    pub fn protocols_str(&self) -> String {
+        self.protocols.map_or_else(|| "any".to_string(), |p| p.to_string())
+    }
+}
+
+/// What the server received and how it answered
+#[derive(Debug, Clone)]
+pub struct AccessEntry {
+    /// When the request reached the router
+    pub timestamp: SystemTime,
+    /// Connection peer (not a forwarded client address)
+    pub peer: Option<SocketAddr>,
+    pub method: String,
+    pub path: String,
+    /// Raw query string, without `?`
+    pub query: String,
+    pub version: HttpVersion,
+    /// None when the request was aborted instead of answered
+    pub status: Option<u16>,
+    /// How the request was failed, when no response was sent
+    pub abort: Option<Abort>,
+    /// Response body bytes (0 for HEAD)
+    pub bytes: usize,
+    pub duration: Duration,
+    /// None when no route matched the path (404)
+    pub route: Option<RouteMatch>,
+}
+
+impl AccessEntry {
+    /// Start an entry for a request as it reaches the router
+    pub fn received(req: &Request) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            peer: req.connection.peer_addr,
+            method: req.method.clone(),
+            path: req.path.clone(),
+            query: req.query.clone(),
+            version: req.version,
+            status: None,
+            abort: None,
+            bytes: 0,
+            duration: Duration::ZERO,
+            route: None,
+        }
+    }
+
+    /// Fill in how the request was answered
+    pub fn responded(&mut self, response: &Response, route: Option<RouteMatch>, duration: Duration) {
+        self.abort = response.abort;
+        self.status = Some(response.status).filter(|_| self.abort.is_none());
+        self.bytes = match (&response.stream, self.abort) {
+            (_, Some(_)) => 0,
+            (Some(stream), None) => stream.len(),
+            (None, None) => response.body.len(),
+        };
+        self.duration = duration;
+        self.route = route;
+    }
+
+    /// The entry as one line, without the trailing newline
+    pub fn format(&self, format: AccessLogFormat) -> String {
+        match format {
+            AccessLogFormat::Json => self.to_json(),
+            AccessLogFormat::Common => self.to_common(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let line = JsonLine {
+            timestamp: rfc3339(self.timestamp),
+            peer: self.peer.map(|peer| peer.to_string()),
+            method: &self.method,
+            path: &self.path,
+            query: &self.query,
+            version: self.version,
+            status: self.status,
+            abort: self.abort.as_ref().map(Abort::as_str),
+            bytes: self.bytes,
+            duration_ms: self.duration.as_secs_f64() * 1000.0,
+            route: self.route.as_ref().map(|route| route.pattern.as_str()),
+            protocols: self.route.as_ref().map(RouteMatch::protocols_str),
+        };
+        serde_json::to_string(&line).expect("access log line serializes")
+    }
+
+    fn to_common(&self) -> String {
+        let host = self.peer.map_or_else(|| "-".to_string(), |peer| peer.ip().to_string());
+        let mut target = self.path.clone();
+        if !self.query.is_empty() {
+            target.push('?');
+            target.push_str(&self.query);
+        }
+        let request_line = format!("{} {} {}", self.method, target, self.version);
+        let protocols = self.route.as_ref().map_or_else(|| "-".to_string(), RouteMatch::protocols_str);
+
+        let mut line = format!(
+            "{} - - [{}] \"{}\" {:03} {} {} {}",
+            host,
+            clf_time(self.timestamp),
+            escape(&request_line),
+            self.status.unwrap_or(0),
+            self.bytes,
+            self.duration.as_millis(),
+            protocols
+        );
+        if let Some(abort) = self.abort {
+            line.push(' ');
+            line.push_str(abort.as_str());
+        }
+        line
+    }
+}
+
+/// JSON form of an entry, fields in log order
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    timestamp: String,
+    peer: Option<String>,
+    method: &'a str,
+    path: &'a str,
+    query: &'a str,
+    version: HttpVersion,
+    status: Option<u16>,
+    abort: Option<&'static str>,
+    bytes: usize,
+    duration_ms: f64,
+    route: Option<&'a str>,
+    protocols: Option<String>,
+}
+
+/// Where access log lines go
+pub struct AccessLog {
+    format: AccessLogFormat,
+    sink: Mutex<Box<dyn Write + Send>>,
+}
+
+impl AccessLog {
+    /// Log to standard output
+    pub fn stdout(format: AccessLogFormat) -> Self {
+        Self::new(format, Box::new(io::stdout()))
+    }
+
+    /// Append to `path`, creating it if needed
+    pub fn file(format: AccessLogFormat, path: &Path) -> io::Result<Self> {
+        let file: File = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(format, Box::new(LineWriter::new(file))))
+    }
+
+    fn new(format: AccessLogFormat, sink: Box<dyn Write + Send>) -> Self {
+        Self {
+            format,
+            sink: Mutex::new(sink),
+        }
+    }
+
+    /// Write one line; failures to write are ignored, like a full disk
+    /// should not fail requests
+    pub fn record(&self, entry: &AccessEntry) {
+        let mut line = entry.format(self.format);
+        line.push('\n');
+        let mut sink = self.sink.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = sink.write_all(line.as_bytes());
+    }
+}
+
+impl std::fmt::Debug for AccessLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessLog").field("format", &self.format).finish_non_exhaustive()
+    }
+}
+
+/// Escape a quoted CLF field: quotes, backslashes and control characters
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\x{:02x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// UTC calendar fields of a point in time
+struct Utc {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u64,
+    minute: u64,
+    second: u64,
+    millis: u32,
+}
+
+impl Utc {
+    fn from(time: SystemTime) -> Self {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let secs = since_epoch.as_secs();
+        let (year, month, day) = civil_from_days((secs / 86400) as i64);
+        Self {
+            year,
+            month,
+            day,
+            hour: secs % 86400 / 3600,
+            minute: secs % 3600 / 60,
+            second: secs % 60,
+            millis: since_epoch.subsec_millis(),
+        }
+    }
+}
+
+/// e.g. `2026-10-17T09:30:00.123Z`
+fn rfc3339(time: SystemTime) -> String {
+    let t = Utc::from(time);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        t.year, t.month, t.day, t.hour, t.minute, t.second, t.millis
+    )
+}
+
+/// e.g. `17/Oct/2026:09:30:00 +0000`
+fn clf_time(time: SystemTime) -> String {
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let t = Utc::from(time);
+    format!(
+        "{:02}/{}/{:04}:{:02}:{:02}:{:02} +0000",
+        t.day,
+        MONTHS[t.month as usize - 1],
+        t.year,
+        t.hour,
+        t.minute,
+        t.second
+    )
+}
+
+/// Year, month and day of a day count since 1970-01-01 (Howard Hinnant's
+/// `civil_from_days`)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> AccessEntry {
+        AccessEntry {
+            // 2024-02-29T13:05:09.250Z
+            timestamp: UNIX_EPOCH + Duration::from_millis(1_709_211_909_250),
+            peer: Some("127.0.0.1:52100".parse().unwrap()),
+            method: "GET".to_string(),
+            path: "/h2/get".to_string(),
+            query: "a=1".to_string(),
+            version: HttpVersion::Http2,
+            status: Some(200),
+            abort: None,
+            bytes: 312,
+            duration: Duration::from_micros(4_500),
+            route: Some(RouteMatch {
+                pattern: "/h2/get".to_string(),
+                protocols: ProtocolSet::parse("h2"),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_common_format() {
+        assert_eq!(
+            entry().format(AccessLogFormat::Common),
+            r#"127.0.0.1 - - [29/Feb/2024:13:05:09 +0000] "GET /h2/get?a=1 HTTP/2" 200 312 4 h2"#
+        );
+
+        let unmatched = AccessEntry {
+            method: "BAD\"METHOD".to_string(),
+            query: String::new(),
+            status: Some(404),
+            route: None,
+            ..entry()
+        };
+        assert_eq!(
+            unmatched.format(AccessLogFormat::Common),
+            r#"127.0.0.1 - - [29/Feb/2024:13:05:09 +0000] "BAD\"METHOD /h2/get HTTP/2" 404 312 4 -"#
+        );
+
+        let mut closed = entry();
+        closed.responded(&Response::aborted(Abort::CloseConnection), closed.route.clone(), closed.duration);
+        assert_eq!(
+            closed.format(AccessLogFormat::Common),
+            r#"127.0.0.1 - - [29/Feb/2024:13:05:09 +0000] "GET /h2/get?a=1 HTTP/2" 000 0 4 h2 close"#
+        );
+    }
+
+    #[test]
+    fn test_json_format() {
+        let line: serde_json::Value = serde_json::from_str(&entry().format(AccessLogFormat::Json)).unwrap();
+        assert_eq!(line["timestamp"], "2024-02-29T13:05:09.250Z");
+        assert_eq!(line["peer"], "127.0.0.1:52100");
+        assert_eq!(line["query"], "a=1");
+        assert_eq!(line["version"], "HTTP/2");
+        assert_eq!(line["status"], 200);
+        assert_eq!(line["bytes"], 312);
+        assert_eq!(line["duration_ms"], 4.5);
+        assert_eq!(line["route"], "/h2/get");
+        assert_eq!(line["protocols"], "h2");
+        assert_eq!(line["abort"], serde_json::Value::Null);
+
+        let any = AccessEntry {
+            route: Some(RouteMatch {
+                pattern: "/get".to_string(),
+                protocols: None,
+            }),
+            ..entry()
+        };
+        let line: serde_json::Value = serde_json::from_str(&any.format(AccessLogFormat::Json)).unwrap();
+        assert_eq!(line["protocols"], "any");
+
+        let mut retry = entry();
+        retry.responded(&Response::aborted(Abort::Http11Required), None, Duration::ZERO);
+        let line: serde_json::Value = serde_json::from_str(&retry.format(AccessLogFormat::Json)).unwrap();
+        assert_eq!(line["status"], serde_json::Value::Null);
+        assert_eq!(line["abort"], "http11-required");
+        assert_eq!(line["bytes"], 0);
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19782), (2024, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+}
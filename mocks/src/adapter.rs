@@ -1,25 +1,68 @@
 //! Adapter to convert between orb-mockhttp and httpbin-handlers types
 
-use orb_mockhttp::{Request as OrbRequest, Response as OrbResponse, ResponseBuilder};
-use httpbin_handlers::{Request, Response};
-use std::collections::HashMap;
+use orb_mockhttp::{Abort as OrbAbort, BodyChunk, Request as OrbRequest, Response as OrbResponse, ResponseBuilder};
+use httpbin_handlers::{Abort, ConnectionInfo, EndpointSettings, Headers, HttpVersion, Params, Request, Response};
+use httpbin_handlers::client_ip::resolve_client_ip;
+use std::sync::Arc;
+use crate::config::MockConfig;
 
 /// Convert orb-mockhttp Request to our Request type
-pub fn to_handler_request(orb_req: &OrbRequest) -> Request {
-    let mut headers = HashMap::new();
+///
+/// The client address is the connection peer, or the address forwarded by
+/// one of `config.trusted_proxies`.
+pub fn to_handler_request(
+    orb_req: &OrbRequest,
+    config: &MockConfig,
+    settings: &Arc<EndpointSettings>,
+) -> Request {
+    let mut headers = Headers::new();
 
-    // Convert headers
+    // Convert headers, keeping repeated values
     for (name, value) in orb_req.headers() {
-        if let Ok(val_str) = value.to_str() {
-            headers.insert(name.to_string(), val_str.to_string());
-        }
+        headers.append(name.as_str(), String::from_utf8_lossy(value.as_bytes()));
     }
 
+    let connection = connection_info(orb_req.connection());
+    let client_ip = connection
+        .peer_addr
+        .map(|peer| resolve_client_ip(peer.ip(), &headers, &config.trusted_proxies));
+
     Request {
         method: orb_req.method().to_string(),
         path: orb_req.uri().path().to_string(),
+        query: orb_req.uri().query().unwrap_or("").to_string(),
         headers,
-        http_version: format!("{:?}", orb_req.version()),
+        version: http_version(orb_req.version()),
+        body: orb_req.body().to_vec(),
+        connection,
+        client_ip,
+        path_params: Params::new(),
+        settings: Arc::clone(settings),
+    }
+}
+
+/// Map an `http::Version` to the handlers' version type
+pub fn http_version(version: http::Version) -> HttpVersion {
+    match version {
+        http::Version::HTTP_09 => HttpVersion::Http09,
+        http::Version::HTTP_10 => HttpVersion::Http10,
+        http::Version::HTTP_2 => HttpVersion::Http2,
+        http::Version::HTTP_3 => HttpVersion::Http3,
+        _ => HttpVersion::Http11,
+    }
+}
+
+fn connection_info(info: &orb_mockhttp::ConnectionInfo) -> ConnectionInfo {
+    ConnectionInfo {
+        peer_addr: info.peer_addr,
+        alpn: info.alpn.clone(),
+        tls_version: info.tls_version.clone(),
+        cipher_suite: info.cipher_suite.clone(),
+        sni: info.sni.clone(),
+        resumed: info.resumed,
+        early_data: info.early_data,
+        stream_id: info.stream_id,
+        peer_certificates: info.peer_certificates.clone(),
     }
 }
 
@@ -28,21 +71,36 @@ pub fn to_orb_response(handler_resp: Response) -> OrbResponse {
     let mut builder = ResponseBuilder::new()
         .status(handler_resp.status);
 
-    // Add headers
-    for (name, value) in handler_resp.headers {
-        builder = builder.header(&name, &value);
+    // Add headers, keeping repeated values (e.g. several Set-Cookie)
+    for (name, value) in handler_resp.headers.iter() {
+        builder = builder.append_header(name, value);
     }
 
-    // Add body
-    builder = builder.body(handler_resp.body);
+    // Add body, streamed chunk by chunk when the handler asks for it
+    builder = match handler_resp.stream {
+        Some(stream) => builder.stream(stream.chunks().iter().map(|chunk| BodyChunk {
+            delay: chunk.delay,
+            data: chunk.data.clone().into(),
+        })),
+        None => builder.body(handler_resp.body),
+    };
+
+    if let Some(abort) = handler_resp.abort {
+        builder = builder.abort(match abort {
+            Abort::Http11Required => OrbAbort::Http11Required,
+            Abort::CloseConnection => OrbAbort::CloseConnection,
+        });
+    }
 
     builder.build()
 }
 
-/// Create a 421 Misdirected Request response
-pub fn misdirected_request() -> OrbResponse {
-    ResponseBuilder::new()
-        .status(421)
-        .body(b"Misdirected Request".to_vec())
-        .build()
+/// Convert a response orb-mockhttp sent itself (status, headers and body)
+pub fn to_handler_response(orb_resp: &OrbResponse) -> Response {
+    let mut response = Response::new(orb_resp.status().as_u16());
+    for (name, value) in orb_resp.headers() {
+        response.headers.append(name.as_str(), String::from_utf8_lossy(value.as_bytes()));
+    }
+    response.body = orb_resp.body().to_vec();
+    response
 }
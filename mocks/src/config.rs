@@ -0,0 +1,28 @@
+//! Settings shared by every registered endpoint
+
+use std::sync::Arc;
+
+use httpbin_handlers::{EndpointSettings, TrustedProxies};
+
+use crate::access_log::AccessLog;
+use crate::metrics::Metrics;
+
+use crate::cors::CorsConfig;
+use crate::protocol::MismatchMode;
+
+/// Per-server settings applied around the handlers
+#[derive(Debug, Clone, Default)]
+pub struct MockConfig {
+    /// Peers whose `Forwarded` / `X-Forwarded-For` headers are believed
+    pub trusted_proxies: TrustedProxies,
+    /// CORS headers added to every response
+    pub cors: CorsConfig,
+    /// How requests over a protocol their route does not allow are rejected
+    pub mismatch: MismatchMode,
+    /// Caps, auth realm and base URL used by the handlers
+    pub endpoints: EndpointSettings,
+    /// Where to log each request, if anywhere
+    pub access_log: Option<Arc<AccessLog>>,
+    /// Request counts and latencies for `/metrics`, if enabled
+    pub metrics: Option<Arc<Metrics>>,
+}
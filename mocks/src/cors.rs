@@ -0,0 +1,151 @@
+//! CORS headers around every endpoint
+//!
+//! Mirrors httpbin: the request `Origin` is echoed back (or `*` without one),
+//! credentials are allowed, and preflights get the requested method and
+//! headers back as allowed.
+
+use httpbin_handlers::{Request, Response};
+
+/// CORS settings for one server instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Add CORS headers at all
+    pub enabled: bool,
+    /// Origins to allow; empty allows (and echoes) any origin
+    pub allowed_origins: Vec<String>,
+    /// Send `Access-Control-Allow-Credentials: true`
+    pub allow_credentials: bool,
+    /// `Access-Control-Max-Age` for preflight responses, in seconds
+    pub max_age: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allowed_origins: Vec::new(),
+            allow_credentials: true,
+            max_age: 3600,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// No CORS headers at all
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    fn allows(&self, origin: &str) -> bool {
+        self.allowed_origins.is_empty() || self.allowed_origins.iter().any(|o| o == origin)
+    }
+}
+
+/// The CORS-relevant parts of a request, taken before it is dispatched
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CorsRequest {
+    origin: Option<String>,
+    /// `Access-Control-Request-Method` of an OPTIONS preflight
+    preflight_method: Option<String>,
+    preflight_headers: Option<String>,
+}
+
+impl CorsRequest {
+    pub(crate) fn new(req: &Request) -> Self {
+        let preflight = req.method == "OPTIONS";
+        let header = |name: &str| req.headers.get(name).map(str::to_string);
+
+        Self {
+            origin: header("Origin"),
+            preflight_method: header("Access-Control-Request-Method").filter(|_| preflight),
+            preflight_headers: header("Access-Control-Request-Headers").filter(|_| preflight),
+        }
+    }
+}
+
+/// Add CORS headers for `req` to `response`
+pub(crate) fn apply(config: &CorsConfig, req: &CorsRequest, response: &mut Response) {
+    if !config.enabled {
+        return;
+    }
+
+    let allow_origin = match &req.origin {
+        Some(origin) if config.allows(origin) => origin.as_str(),
+        Some(_) => return,
+        None if config.allowed_origins.is_empty() => "*",
+        None => return,
+    };
+
+    let headers = &mut response.headers;
+    headers.insert("Access-Control-Allow-Origin", allow_origin);
+    if req.origin.is_some() {
+        headers.append("Vary", "Origin");
+    }
+    // Browsers reject credentials with a wildcard origin
+    if config.allow_credentials && allow_origin != "*" {
+        headers.insert("Access-Control-Allow-Credentials", "true");
+    }
+
+    if let Some(method) = &req.preflight_method {
+        headers.insert("Access-Control-Allow-Methods", method.as_str());
+        if let Some(requested) = &req.preflight_headers {
+            headers.insert("Access-Control-Allow-Headers", requested.as_str());
+        }
+        headers.insert("Access-Control-Max-Age", config.max_age.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cors_headers(config: &CorsConfig, req: &Request) -> Response {
+        let mut response = Response::new(200);
+        apply(config, &CorsRequest::new(req), &mut response);
+        response
+    }
+
+    #[test]
+    fn test_echoes_origin_with_credentials() {
+        let req = Request::new("GET", "/get").with_header("Origin", "https://app.example");
+        let response = cors_headers(&CorsConfig::default(), &req);
+
+        assert_eq!(response.headers.get("Access-Control-Allow-Origin"), Some("https://app.example"));
+        assert_eq!(response.headers.get("Access-Control-Allow-Credentials"), Some("true"));
+        assert_eq!(response.headers.get("Vary"), Some("Origin"));
+        assert_eq!(response.headers.get("Access-Control-Allow-Methods"), None);
+
+        let response = cors_headers(&CorsConfig::default(), &Request::new("GET", "/get"));
+        assert_eq!(response.headers.get("Access-Control-Allow-Origin"), Some("*"));
+        assert_eq!(response.headers.get("Access-Control-Allow-Credentials"), None);
+    }
+
+    #[test]
+    fn test_preflight() {
+        let req = Request::new("OPTIONS", "/h3/put")
+            .with_header("Origin", "https://app.example")
+            .with_header("Access-Control-Request-Method", "PUT")
+            .with_header("Access-Control-Request-Headers", "content-type, x-trace");
+        let response = cors_headers(&CorsConfig::default(), &req);
+
+        assert_eq!(response.headers.get("Access-Control-Allow-Methods"), Some("PUT"));
+        assert_eq!(response.headers.get("Access-Control-Allow-Headers"), Some("content-type, x-trace"));
+        assert_eq!(response.headers.get("Access-Control-Max-Age"), Some("3600"));
+    }
+
+    #[test]
+    fn test_origin_allow_list_and_disabled() {
+        let config = CorsConfig {
+            allowed_origins: vec!["https://app.example".to_string()],
+            ..CorsConfig::default()
+        };
+        let req = Request::new("GET", "/get").with_header("Origin", "https://evil.example");
+        assert_eq!(cors_headers(&config, &req).headers.get("Access-Control-Allow-Origin"), None);
+
+        let req = Request::new("GET", "/get").with_header("Origin", "https://app.example");
+        assert_eq!(cors_headers(&CorsConfig::disabled(), &req).headers.get("Access-Control-Allow-Origin"), None);
+    }
+}
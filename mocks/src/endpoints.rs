@@ -0,0 +1,222 @@
+//! Endpoint registrations
+//! Every endpoint is defined once here, in an [`EndpointGroup`];
+//! `crate::protocol::Mount` decides the prefix and which protocols may reach it
+
+use std::fmt;
+use std::str::FromStr;
+
+use httpbin_handlers as handlers;
+use crate::router::Scope;
+
+/// Helper macro to reduce boilerplate for endpoint registrations
+macro_rules! endpoint {
+    ($scope:expr, $path:expr, ANY, $handler:expr) => {
+        $scope.route_any($path, $handler);
+    };
+    ($scope:expr, $path:expr, $method:expr, $handler:expr) => {
+        $scope.route($method, $path, $handler);
+    };
+}
+
+/// A set of related endpoints that is registered or left out as a whole
+///
+/// The groups follow the sections of httpbin.org's own documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointGroup {
+    /// /get, /post, /put, /patch, /delete
+    HttpMethods,
+    /// /basic-auth, /hidden-basic-auth, /digest-auth, /bearer
+    Auth,
+    /// /status
+    Status,
+    /// /headers, /ip, /user-agent, /protocol, /client-cert
+    RequestInspection,
+    /// /cache, /etag, /response-headers
+    ResponseInspection,
+    /// /json, /xml, /html, /gzip, /brotli, /deflate, /robots.txt, ...
+    ResponseFormats,
+    /// /bytes, /delay, /drip, /stream, /stream-bytes, /range, /links, /uuid, /base64
+    DynamicData,
+    /// /cookies
+    Cookies,
+    /// /image
+    Images,
+    /// /redirect, /redirect-to, /absolute-redirect, /relative-redirect
+    Redirects,
+    /// /anything
+    Anything,
+}
+
+impl EndpointGroup {
+    pub const ALL: [EndpointGroup; 11] = [
+        EndpointGroup::HttpMethods,
+        EndpointGroup::Auth,
+        EndpointGroup::Status,
+        EndpointGroup::RequestInspection,
+        EndpointGroup::ResponseInspection,
+        EndpointGroup::ResponseFormats,
+        EndpointGroup::DynamicData,
+        EndpointGroup::Cookies,
+        EndpointGroup::Images,
+        EndpointGroup::Redirects,
+        EndpointGroup::Anything,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EndpointGroup::HttpMethods => "http-methods",
+            EndpointGroup::Auth => "auth",
+            EndpointGroup::Status => "status",
+            EndpointGroup::RequestInspection => "request-inspection",
+            EndpointGroup::ResponseInspection => "response-inspection",
+            EndpointGroup::ResponseFormats => "response-formats",
+            EndpointGroup::DynamicData => "dynamic-data",
+            EndpointGroup::Cookies => "cookies",
+            EndpointGroup::Images => "images",
+            EndpointGroup::Redirects => "redirects",
+            EndpointGroup::Anything => "anything",
+        }
+    }
+}
+
+impl FromStr for EndpointGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|group| group.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|g| g.as_str()).collect();
+                format!("unknown endpoint group '{}' (expected one of {})", s, names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for EndpointGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Register every endpoint in `groups`
+pub fn register_endpoints(scope: &mut Scope, groups: &[EndpointGroup]) {
+    for group in EndpointGroup::ALL {
+        if groups.contains(&group) {
+            register_group(scope, group);
+        }
+    }
+}
+
+fn register_group(scope: &mut Scope, group: EndpointGroup) {
+    match group {
+        EndpointGroup::HttpMethods => {
+            endpoint!(scope, "/delete", "DELETE", handlers::http_methods::delete_handler);
+            endpoint!(scope, "/get", "GET", handlers::http_methods::get_handler);
+            endpoint!(scope, "/patch", "PATCH", handlers::http_methods::patch_handler);
+            endpoint!(scope, "/post", "POST", handlers::http_methods::post_handler);
+            endpoint!(scope, "/put", "PUT", handlers::http_methods::put_handler);
+        }
+        EndpointGroup::Auth => {
+            endpoint!(scope, "/bearer", "GET", handlers::auth::bearer_handler);
+            endpoint!(scope, "/basic-auth/{user}/{passwd}", "GET", handlers::auth::basic_auth_handler);
+            endpoint!(scope, "/hidden-basic-auth/{user}/{passwd}", "GET", handlers::auth::hidden_basic_auth_handler);
+            endpoint!(scope, "/digest-auth/{qop}/{user}/{passwd}", "GET", handlers::auth::digest_auth_handler);
+            endpoint!(scope, "/digest-auth/{qop}/{user}/{passwd}/{algorithm}", "GET", handlers::auth::digest_auth_algorithm_handler);
+        }
+        EndpointGroup::Status => {
+            endpoint!(scope, "/status/{code}", "GET", handlers::status::status_handler);
+        }
+        EndpointGroup::RequestInspection => {
+            endpoint!(scope, "/headers", "GET", handlers::inspection::headers_handler);
+            endpoint!(scope, "/ip", "GET", handlers::inspection::ip_handler);
+            endpoint!(scope, "/protocol", "GET", handlers::inspection::protocol_handler);
+            endpoint!(scope, "/client-cert", "GET", handlers::inspection::client_cert_handler);
+            endpoint!(scope, "/user-agent", "GET", handlers::inspection::user_agent_handler);
+        }
+        EndpointGroup::ResponseInspection => {
+            endpoint!(scope, "/cache", "GET", handlers::caching::cache_handler);
+            endpoint!(scope, "/response-headers", "GET", handlers::forms::response_headers_handler);
+            endpoint!(scope, "/cache/{n}", "GET", handlers::caching::cache_n_handler);
+            endpoint!(scope, "/etag/{etag}", "GET", handlers::caching::etag_handler);
+        }
+        EndpointGroup::ResponseFormats => {
+            endpoint!(scope, "/brotli", "GET", handlers::compression::brotli_handler);
+            endpoint!(scope, "/deflate", "GET", handlers::compression::deflate_handler);
+            endpoint!(scope, "/deny", "GET", handlers::response_formats::deny_handler);
+            endpoint!(scope, "/encoding/utf8", "GET", handlers::response_formats::encoding_utf8_handler);
+            endpoint!(scope, "/forms/post", "GET", handlers::forms::forms_post_handler);
+            endpoint!(scope, "/gzip", "GET", handlers::compression::gzip_handler);
+            endpoint!(scope, "/html", "GET", handlers::response_formats::html_handler);
+            endpoint!(scope, "/json", "GET", handlers::response_formats::json_handler);
+            endpoint!(scope, "/robots.txt", "GET", handlers::response_formats::robots_txt_handler);
+            endpoint!(scope, "/xml", "GET", handlers::response_formats::xml_handler);
+        }
+        EndpointGroup::DynamicData => {
+            endpoint!(scope, "/drip", "GET", handlers::streaming::drip_handler);
+            endpoint!(scope, "/uuid", "GET", handlers::inspection::uuid_handler);
+            endpoint!(scope, "/bytes/{n}", "GET", handlers::streaming::bytes_handler);
+            endpoint!(scope, "/delay/{n}", "GET", handlers::delay::delay_handler);
+            endpoint!(scope, "/stream/{n}", "GET", handlers::streaming::stream_handler);
+            endpoint!(scope, "/stream-bytes/{n}", "GET", handlers::streaming::stream_bytes_handler);
+            endpoint!(scope, "/range/{n}", "GET", handlers::streaming::range_handler);
+            endpoint!(scope, "/links/{n}/{offset}", "GET", handlers::streaming::links_handler);
+            endpoint!(scope, "/base64/{value}", "GET", handlers::inspection::base64_handler);
+        }
+        EndpointGroup::Cookies => {
+            endpoint!(scope, "/cookies", "GET", handlers::cookies::cookies_handler);
+            endpoint!(scope, "/cookies/delete", "GET", handlers::cookies::cookies_delete_handler);
+            endpoint!(scope, "/cookies/set", "GET", handlers::cookies::cookies_set_handler);
+            endpoint!(scope, "/cookies/set/{name}/{value}", "GET", handlers::cookies::cookies_set_specific_handler);
+        }
+        EndpointGroup::Images => {
+            endpoint!(scope, "/image", "GET", handlers::images::image_handler);
+            endpoint!(scope, "/image/jpeg", "GET", handlers::images::image_jpeg_handler);
+            endpoint!(scope, "/image/png", "GET", handlers::images::image_png_handler);
+            endpoint!(scope, "/image/svg", "GET", handlers::images::image_svg_handler);
+            endpoint!(scope, "/image/webp", "GET", handlers::images::image_webp_handler);
+        }
+        EndpointGroup::Redirects => {
+            endpoint!(scope, "/redirect-to", "GET", handlers::redirect::redirect_to_handler);
+            endpoint!(scope, "/redirect/{n}", "GET", handlers::redirect::redirect_handler);
+            endpoint!(scope, "/absolute-redirect/{n}", "GET", handlers::redirect::absolute_redirect_handler);
+            endpoint!(scope, "/relative-redirect/{n}", "GET", handlers::redirect::relative_redirect_handler);
+        }
+        EndpointGroup::Anything => {
+            endpoint!(scope, "/anything", ANY, handlers::anything::anything_handler);
+            endpoint!(scope, "/anything/{*path}", ANY, handlers::anything::anything_path_handler);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mount, Router};
+    use httpbin_handlers::Request;
+
+    #[test]
+    fn test_parse_group() {
+        assert_eq!("dynamic-data".parse(), Ok(EndpointGroup::DynamicData));
+        assert_eq!(" Auth ".parse(), Ok(EndpointGroup::Auth));
+        assert!("streaming".parse::<EndpointGroup>().is_err());
+
+        for group in EndpointGroup::ALL {
+            assert_eq!(group.to_string().parse(), Ok(group));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_selected_groups() {
+        let mut router = Router::new();
+        register_endpoints(&mut router.mount(&Mount::any()), &[EndpointGroup::Status]);
+        assert_eq!(router.len(), 1);
+
+        let response = router.dispatch(Request::new("GET", "/status/204")).await;
+        assert_eq!(response.status, 204);
+        let response = router.dispatch(Request::new("GET", "/status/abc")).await;
+        assert_eq!(response.status, 400);
+        let response = router.dispatch(Request::new("GET", "/get")).await;
+        assert_eq!(response.status, 404);
+    }
+}
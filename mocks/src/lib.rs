@@ -1,13 +1,31 @@
 //! Mock endpoint registration for httpbin-proto-mock
 //! Using orb-mockhttp with full handler integration
 
+pub mod access_log;
 mod adapter;
-pub mod any;
-pub mod h1;
-pub mod h2;
-pub mod h3;
-
-pub use any::register_any_protocol_mocks;
-pub use h1::register_h1_mocks;
-pub use h2::register_h2_mocks;
-pub use h3::register_h3_mocks;
+pub mod config;
+pub mod cors;
+pub mod endpoints;
+pub mod metrics;
+pub mod protocol;
+pub mod router;
+
+pub use access_log::{AccessLog, AccessLogFormat};
+pub use config::MockConfig;
+pub use cors::CorsConfig;
+pub use endpoints::EndpointGroup;
+pub use metrics::Metrics;
+pub use protocol::{MismatchMode, Mount, Protocol, ProtocolSet};
+pub use router::Router;
+
+/// Register every endpoint under each of `mounts`
+pub fn register_mocks(router: &mut Router, mounts: &[Mount]) {
+    register_groups(router, mounts, &EndpointGroup::ALL);
+}
+
+/// Register the endpoints in `groups` under each of `mounts`
+pub fn register_groups(router: &mut Router, mounts: &[Mount], groups: &[EndpointGroup]) {
+    for mount in mounts {
+        endpoints::register_endpoints(&mut router.mount(mount), groups);
+    }
+}
@@ -0,0 +1,370 @@
+//! Prometheus metrics, served at `/metrics` when enabled
+//!
+//! Counts are kept in memory from the start of the process and rendered in
+//! the text exposition format (version 0.0.4):
+//! - `httpbin_requests_total{route,method,status,version}`, including 413s
+//!   for bodies over the server's size limit
+//! - `httpbin_aborted_requests_total{route,method,abort,version}`: requests
+//!   failed without a response (`close` or `http11-required` mismatches)
+//! - `httpbin_protocol_mismatches_total{route,version}`: requests rejected
+//!   because their route does not accept the protocol (421 by default)
+//! - `httpbin_active_connections{protocol}`: open connections right now
+//! - `httpbin_request_duration_seconds{route,version}`: time to produce the
+//!   response, as a histogram
+//!
+//! `route` is the registered pattern (e.g. `/h2/status/{code}`), so the
+//! label set stays bounded whatever paths clients send; requests no route
+//! matched share `route="unmatched"`. Likewise `method` is one of the
+//! standard methods, or `other` for extension methods (e.g. `PROPFIND` sent
+//! to `/anything`).
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+
+use httpbin_handlers::{Request, Response};
+use orb_mockhttp::{ActiveConnections, HttpProtocol};
+
+use crate::access_log::AccessEntry;
+use crate::router::Router;
+
+/// Upper bounds of the latency histogram buckets, in seconds
+///
+/// They reach past the default `/delay` cap of 10 seconds.
+const BUCKETS: [f64; 12] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+const UNMATCHED: &str = "unmatched";
+
+/// Methods counted under their own name; the rest are `other`
+const METHODS: [&str; 9] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS", "TRACE", "CONNECT"];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    route: String,
+    method: &'static str,
+    status: u16,
+    version: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct AbortKey {
+    route: String,
+    method: &'static str,
+    abort: &'static str,
+    version: &'static str,
+}
+
+/// (route, version)
+type RouteKey = (String, &'static str);
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations per bucket, not cumulative; the last slot is +Inf
+    buckets: [u64; BUCKETS.len() + 1],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        let bucket = BUCKETS.iter().position(|&le| seconds <= le).unwrap_or(BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    requests: BTreeMap<RequestKey, u64>,
+    aborts: BTreeMap<AbortKey, u64>,
+    mismatches: BTreeMap<RouteKey, u64>,
+    durations: BTreeMap<RouteKey, Histogram>,
+}
+
+/// Request counters and latency histograms, plus the server's connection
+/// counts
+#[derive(Debug)]
+pub struct Metrics {
+    counts: Mutex<Counts>,
+    connections: ActiveConnections,
+}
+
+impl Metrics {
+    pub fn new(connections: ActiveConnections) -> Self {
+        Self {
+            counts: Mutex::new(Counts::default()),
+            connections,
+        }
+    }
+
+    /// Count an answered or aborted request
+    pub fn record(&self, entry: &AccessEntry) {
+        let route = entry.route.as_ref().map_or(UNMATCHED, |route| route.pattern.as_str());
+        let method = METHODS.into_iter().find(|m| *m == entry.method).unwrap_or("other");
+        let version = entry.version.as_str();
+        let mismatch = entry
+            .route
+            .as_ref()
+            .and_then(|route| route.protocols)
+            .is_some_and(|protocols| !protocols.allows(entry.version));
+
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        match (entry.status, entry.abort) {
+            (_, Some(abort)) => {
+                let key = AbortKey {
+                    route: route.to_string(),
+                    method,
+                    abort: abort.as_str(),
+                    version,
+                };
+                *counts.aborts.entry(key).or_default() += 1;
+            }
+            (Some(status), None) => {
+                let key = RequestKey {
+                    route: route.to_string(),
+                    method,
+                    status,
+                    version,
+                };
+                *counts.requests.entry(key).or_default() += 1;
+            }
+            (None, None) => {}
+        }
+        if mismatch {
+            *counts.mismatches.entry((route.to_string(), version)).or_default() += 1;
+        }
+        counts
+            .durations
+            .entry((route.to_string(), version))
+            .or_default()
+            .observe(entry.duration.as_secs_f64());
+    }
+
+    /// Everything in the Prometheus text format
+    pub fn render(&self) -> String {
+        let counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        header(&mut out, "httpbin_requests_total", "counter", "Requests answered, by route, method, status and HTTP version");
+        for (key, count) in &counts.requests {
+            let _ = writeln!(
+                out,
+                "httpbin_requests_total{{route=\"{}\",method=\"{}\",status=\"{}\",version=\"{}\"}} {}",
+                escape(&key.route),
+                key.method,
+                key.status,
+                key.version,
+                count
+            );
+        }
+
+        header(
+            &mut out,
+            "httpbin_aborted_requests_total",
+            "counter",
+            "Requests failed without a response, by route, method, abort kind and HTTP version",
+        );
+        for (key, count) in &counts.aborts {
+            let _ = writeln!(
+                out,
+                "httpbin_aborted_requests_total{{route=\"{}\",method=\"{}\",abort=\"{}\",version=\"{}\"}} {}",
+                escape(&key.route),
+                key.method,
+                key.abort,
+                key.version,
+                count
+            );
+        }
+
+        header(
+            &mut out,
+            "httpbin_protocol_mismatches_total",
+            "counter",
+            "Requests rejected because their route does not accept the protocol",
+        );
+        for ((route, version), count) in &counts.mismatches {
+            let _ = writeln!(
+                out,
+                "httpbin_protocol_mismatches_total{{route=\"{}\",version=\"{}\"}} {}",
+                escape(route),
+                version,
+                count
+            );
+        }
+
+        header(&mut out, "httpbin_active_connections", "gauge", "Open client connections, by protocol");
+        for (protocol, name) in [(HttpProtocol::Http1, "h1"), (HttpProtocol::Http2, "h2"), (HttpProtocol::Http3, "h3")] {
+            let _ = writeln!(
+                out,
+                "httpbin_active_connections{{protocol=\"{}\"}} {}",
+                name,
+                self.connections.get(protocol)
+            );
+        }
+
+        header(
+            &mut out,
+            "httpbin_request_duration_seconds",
+            "histogram",
+            "Time to produce a response, by route and HTTP version",
+        );
+        for ((route, version), histogram) in &counts.durations {
+            let labels = format!("route=\"{}\",version=\"{}\"", escape(route), version);
+            let mut cumulative = 0;
+            for (le, count) in BUCKETS.iter().zip(&histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(out, "httpbin_request_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, le, cumulative);
+            }
+            let _ = writeln!(
+                out,
+                "httpbin_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                labels, histogram.count
+            );
+            let _ = writeln!(out, "httpbin_request_duration_seconds_sum{{{}}} {}", labels, histogram.sum);
+            let _ = writeln!(out, "httpbin_request_duration_seconds_count{{{}}} {}", labels, histogram.count);
+        }
+
+        out
+    }
+
+    /// Serve [`Metrics::render`] at `GET /metrics`, for every protocol
+    pub fn register(self: &Arc<Self>, router: &mut Router) {
+        let metrics = Arc::clone(self);
+        router.route("GET", "/metrics", move |_: &Request| {
+            let mut response = Response::new(200).with_text(&metrics.render());
+            response.headers.insert("Content-Type", "text/plain; version=0.0.4; charset=utf-8");
+            response
+        });
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value: backslash, double quote and newline
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access_log::RouteMatch;
+    use crate::protocol::ProtocolSet;
+    use httpbin_handlers::{Abort, HttpVersion};
+    use std::time::Duration;
+
+    fn entry(path: &str, version: HttpVersion, status: u16, millis: u64, protocols: Option<&str>) -> AccessEntry {
+        let mut req = Request::new("GET", path);
+        req.version = version;
+        let mut entry = AccessEntry::received(&req);
+        let route = protocols.map(|p| RouteMatch {
+            pattern: path.to_string(),
+            protocols: Some(p).filter(|p| *p != "any").and_then(ProtocolSet::parse),
+        });
+        entry.responded(&Response::new(status), route, Duration::from_millis(millis));
+        entry
+    }
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::new(ActiveConnections::default());
+        metrics.record(&entry("/get", HttpVersion::Http11, 200, 3, Some("any")));
+        metrics.record(&entry("/get", HttpVersion::Http11, 200, 700, Some("any")));
+        metrics.record(&entry("/h2/get", HttpVersion::Http11, 421, 0, Some("h2")));
+        metrics.record(&entry("/nope\"", HttpVersion::Http3, 404, 0, None));
+
+        let text = metrics.render();
+        let has = |line: &str| text.lines().any(|l| l == line);
+
+        assert!(has("# TYPE httpbin_requests_total counter"));
+        assert!(has(r#"httpbin_requests_total{route="/get",method="GET",status="200",version="HTTP/1.1"} 2"#));
+        assert!(has(r#"httpbin_requests_total{route="/h2/get",method="GET",status="421",version="HTTP/1.1"} 1"#));
+        assert!(has(r#"httpbin_requests_total{route="unmatched",method="GET",status="404",version="HTTP/3"} 1"#));
+        assert!(has(r#"httpbin_protocol_mismatches_total{route="/h2/get",version="HTTP/1.1"} 1"#));
+        assert!(!text.contains(r#"httpbin_protocol_mismatches_total{route="/get""#));
+        assert!(has(r#"httpbin_active_connections{protocol="h3"} 0"#));
+        assert!(has("# TYPE httpbin_aborted_requests_total counter"));
+        assert!(!text.contains("httpbin_aborted_requests_total{"));
+
+        assert!(has(r#"httpbin_request_duration_seconds_bucket{route="/get",version="HTTP/1.1",le="0.001"} 0"#));
+        assert!(has(r#"httpbin_request_duration_seconds_bucket{route="/get",version="HTTP/1.1",le="0.005"} 1"#));
+        assert!(has(r#"httpbin_request_duration_seconds_bucket{route="/get",version="HTTP/1.1",le="1"} 2"#));
+        assert!(has(r#"httpbin_request_duration_seconds_bucket{route="/get",version="HTTP/1.1",le="+Inf"} 2"#));
+        assert!(has(r#"httpbin_request_duration_seconds_count{route="/get",version="HTTP/1.1"} 2"#));
+    }
+
+    #[test]
+    fn test_bounded_labels() {
+        let metrics = Metrics::new(ActiveConnections::default());
+        for method in ["PROPFIND", "QUERY", "get"] {
+            let mut req = Request::new(method, "/anything");
+            req.version = HttpVersion::Http11;
+            let mut entry = AccessEntry::received(&req);
+            entry.responded(&Response::new(200), None, Duration::ZERO);
+            metrics.record(&entry);
+        }
+        let mut closed = entry("/h2/get", HttpVersion::Http11, 0, 0, Some("h2"));
+        closed.responded(&Response::aborted(Abort::CloseConnection), closed.route.clone(), Duration::ZERO);
+        metrics.record(&closed);
+
+        let text = metrics.render();
+        let has = |line: &str| text.lines().any(|l| l == line);
+        assert!(has(r#"httpbin_requests_total{route="unmatched",method="other",status="200",version="HTTP/1.1"} 3"#));
+        assert!(has(r#"httpbin_aborted_requests_total{route="/h2/get",method="GET",abort="close",version="HTTP/1.1"} 1"#));
+        assert!(has(r#"httpbin_protocol_mismatches_total{route="/h2/get",version="HTTP/1.1"} 1"#));
+        assert!(!text.contains(r#"route="/h2/get",method="GET",status="#));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let metrics = Arc::new(Metrics::new(ActiveConnections::default()));
+        let mut router = Router::new();
+        metrics.register(&mut router);
+
+        let response = router.dispatch(Request::new("GET", "/metrics")).await;
+        assert_eq!(response.status, 200);
+        assert!(response.headers.get("Content-Type").unwrap().starts_with("text/plain; version=0.0.4"));
+        assert!(String::from_utf8(response.body).unwrap().contains("# TYPE httpbin_active_connections gauge"));
+    }
+
+    #[test]
+    fn test_oversized_body_is_counted() {
+        use crate::config::MockConfig;
+        use orb_mockhttp::{RequestLimits, TestServerBuilder};
+        use std::io::{Read, Write};
+
+        let server = TestServerBuilder::new()
+            .with_protocols(&[HttpProtocol::Http1])
+            .without_tls()
+            .with_request_limits(RequestLimits { max_body_size: Some(4), ..Default::default() })
+            .build();
+        let metrics = Arc::new(Metrics::new(server.active_connections()));
+        let mut router = Router::new();
+        metrics.register(&mut router);
+        router.route("POST", "/post", |_: &Request| Response::new(200));
+        let config = MockConfig {
+            metrics: Some(Arc::clone(&metrics)),
+            ..Default::default()
+        };
+        router.install(&server, &config);
+
+        let exchange = |request: &str| {
+            let mut stream = std::net::TcpStream::connect(server.address()).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = exchange("POST /post HTTP/1.1\r\nHost: x\r\nContent-Length: 10\r\nConnection: close\r\n\r\n0123456789");
+        assert!(response.starts_with("HTTP/1.1 413"));
+
+        let text = exchange("GET /metrics HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n");
+        let has = |line: &str| text.lines().any(|l| l == line);
+        assert!(has(r#"httpbin_requests_total{route="/post",method="POST",status="413",version="HTTP/1.1"} 1"#), "{}", text);
+        assert!(has(r#"httpbin_request_duration_seconds_count{route="/post",version="HTTP/1.1"} 1"#), "{}", text);
+    }
+}
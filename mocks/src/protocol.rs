@@ -0,0 +1,254 @@
+//! Protocol constraints for mounted endpoint groups
+//!
+//! A [`Mount`] places the full endpoint set under a path prefix and limits it
+//! to a [`ProtocolSet`]: `/h1` only answers HTTP/1.x, `/h2h3` answers HTTP/2
+//! and HTTP/3, and so on. Requests over any other protocol are rejected the
+//! way the [`MismatchMode`] says.
+
+use std::fmt;
+use std::str::FromStr;
+
+use httpbin_handlers::{Abort, HttpVersion, Response};
+
+/// HTTP protocol family, as used in mount prefixes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    /// HTTP/1.0 and HTTP/1.1
+    H1,
+    H2,
+    H3,
+}
+
+impl Protocol {
+    pub fn of(version: HttpVersion) -> Self {
+        match version {
+            HttpVersion::Http09 | HttpVersion::Http10 | HttpVersion::Http11 => Protocol::H1,
+            HttpVersion::Http2 => Protocol::H2,
+            HttpVersion::Http3 => Protocol::H3,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::H1 => "h1",
+            Protocol::H2 => "h2",
+            Protocol::H3 => "h3",
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        match self {
+            Protocol::H1 => 1,
+            Protocol::H2 => 2,
+            Protocol::H3 => 4,
+        }
+    }
+}
+
+/// A non-empty set of allowed protocols
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProtocolSet(u8);
+
+impl ProtocolSet {
+    pub fn only(protocol: Protocol) -> Self {
+        Self(protocol.bit())
+    }
+
+    pub fn with(self, protocol: Protocol) -> Self {
+        Self(self.0 | protocol.bit())
+    }
+
+    pub fn contains(&self, protocol: Protocol) -> bool {
+        self.0 & protocol.bit() != 0
+    }
+
+    pub fn allows(&self, version: HttpVersion) -> bool {
+        self.contains(Protocol::of(version))
+    }
+
+    /// Parse a set written as concatenated names, e.g. "h1", "h2h3"
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut rest = s;
+        let mut set = 0;
+
+        while !rest.is_empty() {
+            let protocol = [Protocol::H1, Protocol::H2, Protocol::H3]
+                .into_iter()
+                .find(|p| rest.starts_with(p.as_str()))?;
+            if set & protocol.bit() != 0 {
+                return None;
+            }
+            set |= protocol.bit();
+            rest = &rest[protocol.as_str().len()..];
+        }
+
+        (set != 0).then_some(Self(set))
+    }
+}
+
+impl fmt::Display for ProtocolSet {
+    /// Canonical name, e.g. "h2h3"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for protocol in [Protocol::H1, Protocol::H2, Protocol::H3] {
+            if self.contains(protocol) {
+                f.write_str(protocol.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the endpoint set is mounted and which protocols may reach it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    /// Path prefix without a trailing slash ("" for the root)
+    pub prefix: String,
+    /// Allowed protocols, or None for any
+    pub protocols: Option<ProtocolSet>,
+}
+
+impl Mount {
+    /// Mount at the root, open to every protocol
+    pub fn any() -> Self {
+        Self {
+            prefix: String::new(),
+            protocols: None,
+        }
+    }
+
+    /// Mount under `/{name}` (e.g. `/h2h3`), limited to those protocols
+    pub fn only(protocols: ProtocolSet) -> Self {
+        Self {
+            prefix: format!("/{}", protocols),
+            protocols: Some(protocols),
+        }
+    }
+
+    /// Parse a mount name: "any" (or "/") for the root, else a protocol set
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim_matches('/') {
+            "" | "any" => Some(Self::any()),
+            name => ProtocolSet::parse(name).map(Self::only),
+        }
+    }
+
+    /// `/`, `/h1`, `/h2` and `/h3`
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::any(),
+            Self::only(ProtocolSet::only(Protocol::H1)),
+            Self::only(ProtocolSet::only(Protocol::H2)),
+            Self::only(ProtocolSet::only(Protocol::H3)),
+        ]
+    }
+}
+
+/// How a request over a protocol its route does not allow is rejected
+///
+/// Set per server in `MockConfig::mismatch`; a request can pick another mode
+/// with the `mismatch` query parameter (e.g. `/h1/get?mismatch=505`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MismatchMode {
+    /// 421 Misdirected Request
+    #[default]
+    Misdirected,
+    /// 505 HTTP Version Not Supported
+    VersionNotSupported,
+    /// Stream error asking for HTTP/1.1 (see `Abort::Http11Required`)
+    Http11Required,
+    /// Close the connection without a response
+    CloseConnection,
+}
+
+impl MismatchMode {
+    /// Query parameter that overrides the mode for one request
+    pub const QUERY_PARAM: &'static str = "mismatch";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MismatchMode::Misdirected => "421",
+            MismatchMode::VersionNotSupported => "505",
+            MismatchMode::Http11Required => "http11-required",
+            MismatchMode::CloseConnection => "close",
+        }
+    }
+
+    /// The response that rejects the request
+    pub fn response(&self) -> Response {
+        match self {
+            MismatchMode::Misdirected => Response::new(421).with_text("Misdirected Request"),
+            MismatchMode::VersionNotSupported => {
+                Response::new(505).with_text("HTTP Version Not Supported")
+            }
+            MismatchMode::Http11Required => Response::aborted(Abort::Http11Required),
+            MismatchMode::CloseConnection => Response::aborted(Abort::CloseConnection),
+        }
+    }
+}
+
+impl FromStr for MismatchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "421" | "misdirected" => Ok(MismatchMode::Misdirected),
+            "505" | "version-not-supported" => Ok(MismatchMode::VersionNotSupported),
+            "http11-required" | "http_1_1_required" => Ok(MismatchMode::Http11Required),
+            "close" => Ok(MismatchMode::CloseConnection),
+            _ => Err(format!(
+                "unknown mismatch mode '{}' (expected 421, 505, http11-required or close)",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for MismatchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_protocol_set() {
+        let set = ProtocolSet::parse("h3h2").unwrap();
+        assert!(set.allows(HttpVersion::Http2));
+        assert!(set.allows(HttpVersion::Http3));
+        assert!(!set.allows(HttpVersion::Http11));
+        assert_eq!(set.to_string(), "h2h3");
+
+        assert!(ProtocolSet::parse("h1").unwrap().allows(HttpVersion::Http10));
+        assert!(ProtocolSet::parse("").is_none());
+        assert!(ProtocolSet::parse("h2h2").is_none());
+        assert!(ProtocolSet::parse("h4").is_none());
+    }
+
+    #[test]
+    fn test_parse_mount() {
+        assert_eq!(Mount::parse("/"), Some(Mount::any()));
+        assert_eq!(Mount::parse("h1h3").unwrap().prefix, "/h1h3");
+        assert_eq!(Mount::parse("/h2/").unwrap().prefix, "/h2");
+        assert!(Mount::parse("http2").is_none());
+    }
+
+    #[test]
+    fn test_mismatch_mode() {
+        assert_eq!("505".parse(), Ok(MismatchMode::VersionNotSupported));
+        assert_eq!("HTTP_1_1_REQUIRED".parse(), Ok(MismatchMode::Http11Required));
+        assert!("418".parse::<MismatchMode>().is_err());
+
+        for mode in [MismatchMode::Misdirected, MismatchMode::CloseConnection] {
+            assert_eq!(mode.to_string().parse(), Ok(mode));
+        }
+
+        assert_eq!(MismatchMode::default().response().status, 421);
+        assert_eq!(
+            MismatchMode::Http11Required.response().abort,
+            Some(Abort::Http11Required)
+        );
+    }
+}
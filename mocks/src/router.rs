@@ -0,0 +1,609 @@
+//! Path-pattern router in front of the handlers
+//!
+//! Patterns are matched segment by segment:
+//! - `literal` matches itself
+//! - `{name}` matches any single non-empty segment
+//! - `{*name}` matches the rest of the path (one or more segments) and must
+//!   come last
+//!
+//! Captured values are percent-decoded into `Request::path_params`.
+//! Routes are tried in registration order and the first match wins.
+//!
+//! A path that matches some route, but not for the request method, gets 405
+//! Method Not Allowed with an `Allow` header listing the methods that route
+//! table has for it. Routes added with `route_any` accept every method,
+//! including extension methods such as QUERY or PROPFIND.
+//!
+//! Routes registered through [`Router::mount`] carry the mount's protocol
+//! constraint; a matching request over another protocol is rejected as the
+//! [`MismatchMode`] says (421 Misdirected Request by default) instead of
+//! reaching the handler.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use httpbin_handlers::{BodyStream, Handler, IntoHandler, Params, Request, Response};
+use orb_mockhttp::TestServer;
+
+use crate::access_log::{AccessEntry, RouteMatch};
+use crate::adapter::{to_handler_request, to_handler_response, to_orb_response};
+use crate::config::MockConfig;
+use crate::cors::{self, CorsRequest};
+use crate::protocol::{MismatchMode, Mount, ProtocolSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+    CatchAll(String),
+}
+
+/// A parsed route pattern such as `/status/{code}` or `/anything/{*path}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl Pattern {
+    /// Parse a pattern
+    ///
+    /// Panics on malformed patterns, like an invalid route registration in
+    /// orb-mockhttp: they are programming errors.
+    pub fn parse(pattern: &str) -> Self {
+        let body = pattern
+            .strip_prefix('/')
+            .unwrap_or_else(|| panic!("Route pattern must start with '/': {}", pattern));
+
+        let segments: Vec<Segment> = body
+            .split('/')
+            .map(|segment| {
+                match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(name) => match name.strip_prefix('*') {
+                        Some(name) => Segment::CatchAll(name.to_string()),
+                        None => Segment::Param(name.to_string()),
+                    },
+                    None => Segment::Literal(segment.to_string()),
+                }
+            })
+            .collect();
+
+        let catch_all = segments
+            .iter()
+            .position(|s| matches!(s, Segment::CatchAll(_)));
+        if let Some(i) = catch_all {
+            assert!(
+                i == segments.len() - 1,
+                "Catch-all segment must be last in route pattern: {}",
+                pattern
+            );
+        }
+
+        Self {
+            source: pattern.to_string(),
+            segments,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Match a request path, returning the captured parameters
+    pub fn matches(&self, path: &str) -> Option<Params> {
+        let mut parts = path.strip_prefix('/')?.split('/');
+        let mut params = Params::new();
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => {
+                    if parts.next()? != literal {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    let value = parts.next().filter(|v| !v.is_empty())?;
+                    params.push(name.as_str(), httpbin_handlers::percent_decode(value));
+                }
+                Segment::CatchAll(name) => {
+                    let rest: Vec<&str> = parts.by_ref().collect();
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    params.push(name.as_str(), httpbin_handlers::percent_decode(&rest.join("/")));
+                }
+            }
+        }
+
+        // Every path segment must be consumed
+        match parts.next() {
+            Some(_) => None,
+            None => Some(params),
+        }
+    }
+}
+
+struct Route {
+    /// None matches every method
+    method: Option<String>,
+    pattern: Pattern,
+    protocols: Option<ProtocolSet>,
+    handler: Arc<dyn Handler>,
+}
+
+impl Route {
+    fn to_match(&self) -> RouteMatch {
+        RouteMatch {
+            pattern: self.pattern.as_str().to_string(),
+            protocols: self.protocols,
+        }
+    }
+}
+
+/// Route table mapping (method, path pattern) to handlers
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+    mismatch: MismatchMode,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how protocol mismatches are rejected when the request does not
+    /// pick a mode itself
+    pub fn mismatch_mode(&mut self, mode: MismatchMode) -> &mut Self {
+        self.mismatch = mode;
+        self
+    }
+
+    /// Register a handler for `method` on paths matching `pattern`
+    pub fn route<M, H>(&mut self, method: &str, pattern: &str, handler: H) -> &mut Self
+    where
+        H: IntoHandler<M>,
+    {
+        self.push(Some(method), Pattern::parse(pattern), None, handler);
+        self
+    }
+
+    /// Register a handler for every method on paths matching `pattern`
+    pub fn route_any<M, H>(&mut self, pattern: &str, handler: H) -> &mut Self
+    where
+        H: IntoHandler<M>,
+    {
+        self.push(None, Pattern::parse(pattern), None, handler);
+        self
+    }
+
+    /// Register routes under `mount.prefix`, limited to `mount.protocols`
+    pub fn mount(&mut self, mount: &Mount) -> Scope<'_> {
+        Scope {
+            router: self,
+            prefix: mount.prefix.clone(),
+            protocols: mount.protocols,
+        }
+    }
+
+    fn push<M, H>(&mut self, method: Option<&str>, pattern: Pattern, protocols: Option<ProtocolSet>, handler: H)
+    where
+        H: IntoHandler<M>,
+    {
+        self.routes.push(Route {
+            method: method.map(str::to_string),
+            pattern,
+            protocols,
+            handler: Arc::new(handler.into_handler()),
+        });
+    }
+
+    /// Number of registered routes
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Find the handler for a request and run it, or answer 404/405
+    ///
+    /// HEAD falls back to the GET route, and OPTIONS is answered from the
+    /// route table unless a route registers it. Every HEAD response has its
+    /// body stripped.
+    pub async fn dispatch(&self, req: Request) -> Response {
+        self.dispatch_route(req).await.0
+    }
+
+    /// Like [`Router::dispatch`], also returning the route the path matched
+    ///
+    /// That is the route whose handler ran, or the one that rejected the
+    /// request (405, or a protocol mismatch); None for 404.
+    pub async fn dispatch_route(&self, req: Request) -> (Response, Option<RouteMatch>) {
+        let head = req.method == "HEAD";
+        let (response, route) = self.respond(req).await;
+        let response = if head { strip_body(response) } else { response };
+        (response, route.map(Route::to_match))
+    }
+
+    async fn respond(&self, mut req: Request) -> (Response, Option<&Route>) {
+        let matched: Vec<(&Route, Params)> = self
+            .routes
+            .iter()
+            .filter_map(|route| route.pattern.matches(&req.path).map(|params| (route, params)))
+            .collect();
+
+        if matched.is_empty() {
+            return (Response::new(404), None);
+        }
+        let first = matched[0].0;
+
+        let matched: Vec<(&Route, Params)> = matched
+            .into_iter()
+            .filter(|(route, _)| route.protocols.is_none_or(|p| p.allows(req.version)))
+            .collect();
+
+        if matched.is_empty() {
+            let mode = req
+                .args()
+                .get(MismatchMode::QUERY_PARAM)
+                .and_then(|mode| mode.parse().ok())
+                .unwrap_or(self.mismatch);
+            return (mode.response(), Some(first));
+        }
+
+        let routes = || matched.iter().map(|(route, _)| *route);
+        let explicit = |method: &str| matched.iter().find(|(route, _)| route.method.as_deref() == Some(method));
+
+        if req.method == "OPTIONS" && explicit("OPTIONS").is_none() {
+            let mut response = Response::new(200);
+            response.headers.insert("Allow", allowed_methods(routes()).join(", "));
+            return (response, Some(matched[0].0));
+        }
+
+        let found = matched
+            .iter()
+            .find(|(route, _)| route.method.as_deref().is_none_or(|m| m == req.method))
+            .or_else(|| match req.method.as_str() {
+                "HEAD" => explicit("GET"),
+                _ => None,
+            });
+
+        match found {
+            Some((route, params)) => {
+                req.path_params = params.clone();
+                (route.handler.call(req).await, Some(*route))
+            }
+            None => {
+                let mut response = Response::new(405);
+                response.headers.insert("Allow", allowed_methods(routes()).join(", "));
+                (response, Some(matched[0].0))
+            }
+        }
+    }
+
+    /// The route a request would be logged under, without running it
+    ///
+    /// Prefers a route whose protocols allow the request, as dispatching does.
+    fn route_for(&self, req: &Request) -> Option<RouteMatch> {
+        let matched: Vec<&Route> = self
+            .routes
+            .iter()
+            .filter(|route| route.pattern.matches(&req.path).is_some())
+            .collect();
+        matched
+            .iter()
+            .find(|route| route.protocols.is_none_or(|p| p.allows(req.version)))
+            .or(matched.first())
+            .map(|route| route.to_match())
+    }
+
+    /// Serve every request on `server` through this router
+    ///
+    /// Requests orb-mockhttp rejects itself (a body over the size limit) are
+    /// logged and counted too, under the route their path matches.
+    pub fn install(mut self, server: &TestServer, config: &MockConfig) {
+        self.mismatch_mode(config.mismatch);
+        let router = Arc::new(self);
+        let settings = Arc::new(config.endpoints.clone());
+        let config = Arc::new(config.clone());
+
+        if config.access_log.is_some() || config.metrics.is_some() {
+            let router = Arc::clone(&router);
+            let config = Arc::clone(&config);
+            let settings = Arc::clone(&settings);
+            server.on_rejected_fn(move |orb_req, orb_resp, elapsed| {
+                let req = to_handler_request(orb_req, &config, &settings);
+                let mut entry = AccessEntry::received(&req);
+                entry.responded(&to_handler_response(orb_resp), router.route_for(&req), elapsed);
+                observe(&config, &entry);
+            });
+        }
+
+        server.on_unmatched_async_fn(move |orb_req| {
+            let router = Arc::clone(&router);
+            let config = Arc::clone(&config);
+            let settings = Arc::clone(&settings);
+            async move {
+                let started = Instant::now();
+                let req = to_handler_request(&orb_req, &config, &settings);
+                let cors_req = CorsRequest::new(&req);
+                let observed = config.access_log.is_some() || config.metrics.is_some();
+                let entry = observed.then(|| AccessEntry::received(&req));
+
+                let (mut response, route) = router.dispatch_route(req).await;
+                cors::apply(&config.cors, &cors_req, &mut response);
+
+                if let Some(mut entry) = entry {
+                    entry.responded(&response, route, started.elapsed());
+                    observe(&config, &entry);
+                }
+                to_orb_response(response)
+            }
+        });
+    }
+}
+
+/// Write an answered request to the access log and metrics, where enabled
+fn observe(config: &MockConfig, entry: &AccessEntry) {
+    if let Some(log) = &config.access_log {
+        log.record(entry);
+    }
+    if let Some(metrics) = &config.metrics {
+        metrics.record(entry);
+    }
+}
+
+/// Methods advertised for routes that accept any method
+const ANY_METHODS: [&str; 8] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS", "TRACE"];
+
+/// Methods `routes` answer, in registration order, plus the implied HEAD
+/// (for GET) and OPTIONS
+fn allowed_methods<'r>(routes: impl Iterator<Item = &'r Route>) -> Vec<&'r str> {
+    let mut allow: Vec<&str> = Vec::new();
+    let mut add = |method| {
+        if !allow.contains(&method) {
+            allow.push(method);
+        }
+    };
+
+    for route in routes {
+        match route.method.as_deref() {
+            Some(method) => {
+                add(method);
+                if method == "GET" {
+                    add("HEAD");
+                }
+            }
+            None => ANY_METHODS.into_iter().for_each(&mut add),
+        }
+    }
+    add("OPTIONS");
+
+    allow
+}
+
+/// Turn a GET response into the matching HEAD response
+///
+/// Headers stay as they are and Content-Length still describes the body that
+/// GET would send. Streamed bodies have no Content-Length, so they become an
+/// empty stream.
+fn strip_body(mut response: Response) -> Response {
+    match response.stream.take() {
+        Some(_) => response.with_stream(BodyStream::new()),
+        None => {
+            if response.headers.get("Content-Length").is_none() {
+                response.headers.insert("Content-Length", response.body.len().to_string());
+            }
+            response.body.clear();
+            response
+        }
+    }
+}
+
+/// Route registration under a mount prefix and protocol constraint
+pub struct Scope<'r> {
+    router: &'r mut Router,
+    prefix: String,
+    protocols: Option<ProtocolSet>,
+}
+
+impl Scope<'_> {
+    /// Register a handler for `method` on `prefix` + `pattern`
+    pub fn route<M, H>(&mut self, method: &str, pattern: &str, handler: H) -> &mut Self
+    where
+        H: IntoHandler<M>,
+    {
+        let pattern = Pattern::parse(&format!("{}{}", self.prefix, pattern));
+        self.router.push(Some(method), pattern, self.protocols, handler);
+        self
+    }
+
+    /// Register a handler for every method on `prefix` + `pattern`
+    pub fn route_any<M, H>(&mut self, pattern: &str, handler: H) -> &mut Self
+    where
+        H: IntoHandler<M>,
+    {
+        let pattern = Pattern::parse(&format!("{}{}", self.prefix, pattern));
+        self.router.push(None, pattern, self.protocols, handler);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpbin_handlers::{Abort, HttpVersion};
+
+    fn echo_params(req: &Request) -> Response {
+        Response::new(200).with_json(&req.path_params)
+    }
+
+    #[test]
+    fn test_literal_and_param_segments() {
+        let pattern = Pattern::parse("/basic-auth/{user}/{passwd}");
+
+        let params = pattern.matches("/basic-auth/a%40b/p%2Fw").unwrap();
+        assert_eq!(params.get("user"), Some("a@b"));
+        assert_eq!(params.get("passwd"), Some("p/w"));
+
+        assert!(pattern.matches("/basic-auth/user").is_none());
+        assert!(pattern.matches("/basic-auth/user/").is_none());
+        assert!(pattern.matches("/basic-auth/user/pass/extra").is_none());
+        assert!(pattern.matches("/hidden-basic-auth/user/pass").is_none());
+    }
+
+    #[test]
+    fn test_catch_all_segment() {
+        let pattern = Pattern::parse("/anything/{*path}");
+
+        let params = pattern.matches("/anything/a/b/c").unwrap();
+        assert_eq!(params.get("path"), Some("a/b/c"));
+        assert!(pattern.matches("/anything").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "must be last")]
+    fn test_catch_all_must_be_last() {
+        Pattern::parse("/files/{*path}/raw");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch() {
+        let mut router = Router::new();
+        router.route("GET", "/status/{code}", echo_params);
+
+        let response = router.dispatch(Request::new("GET", "/status/599")).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, br#"{"code":"599"}"#);
+
+        let response = router.dispatch(Request::new("POST", "/status/599")).await;
+        assert_eq!(response.status, 405);
+
+        let response = router.dispatch(Request::new("GET", "/status")).await;
+        assert_eq!(response.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        let mut router = Router::new();
+        router
+            .route("GET", "/cookies/set", echo_params)
+            .route("PUT", "/cookies/set", echo_params)
+            .route("GET", "/cookies/set/{name}/{value}", echo_params);
+
+        let response = router.dispatch(Request::new("POST", "/cookies/set")).await;
+        assert_eq!(response.status, 405);
+        assert_eq!(response.headers.get("Allow"), Some("GET, HEAD, PUT, OPTIONS"));
+    }
+
+    #[tokio::test]
+    async fn test_head_and_options() {
+        let mut router = Router::new();
+        router.route("GET", "/bytes/{n}", |_: &Request| Response::new(200).with_text("12345"));
+
+        let response = router.dispatch(Request::new("HEAD", "/bytes/5")).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.headers.get("Content-Length"), Some("5"));
+        assert!(response.body.is_empty());
+
+        let response = router.dispatch(Request::new("OPTIONS", "/bytes/5")).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.headers.get("Allow"), Some("GET, HEAD, OPTIONS"));
+    }
+
+    #[tokio::test]
+    async fn test_route_any_method() {
+        let mut router = Router::new();
+        router.route_any("/anything/{*path}", echo_params);
+
+        for method in ["GET", "DELETE", "QUERY", "PROPFIND", "TRACE"] {
+            let response = router.dispatch(Request::new(method, "/anything/x")).await;
+            assert_eq!(response.status, 200, "{}", method);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mount_protocol_constraint() {
+        let mut router = Router::new();
+        let mount = Mount::parse("h2h3").unwrap();
+        router.mount(&mount).route("GET", "/status/{code}", echo_params);
+
+        let h2 = Request {
+            version: HttpVersion::Http2,
+            ..Request::new("GET", "/h2h3/status/204")
+        };
+        let response = router.dispatch(h2).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, br#"{"code":"204"}"#);
+
+        let h1 = Request::new("GET", "/h2h3/status/204");
+        assert_eq!(router.dispatch(h1).await.status, 421);
+
+        let h1 = Request::new("GET", "/h2h3/status/204?mismatch=505");
+        assert_eq!(router.dispatch(h1).await.status, 505);
+
+        router.mismatch_mode(MismatchMode::CloseConnection);
+        let h1 = Request::new("GET", "/h2h3/status/204");
+        assert_eq!(router.dispatch(h1).await.abort, Some(Abort::CloseConnection));
+
+        let unprefixed = Request::new("GET", "/status/204");
+        assert_eq!(router.dispatch(unprefixed).await.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_route() {
+        let mut router = Router::new();
+        router.route("GET", "/status/{code}", echo_params);
+        router.mount(&Mount::parse("h2").unwrap()).route("GET", "/get", echo_params);
+
+        let (response, route) = router.dispatch_route(Request::new("HEAD", "/status/204")).await;
+        assert_eq!(response.status, 200);
+        let route = route.unwrap();
+        assert_eq!(route.pattern, "/status/{code}");
+        assert_eq!(route.protocols_str(), "any");
+
+        let (response, route) = router.dispatch_route(Request::new("GET", "/h2/get")).await;
+        assert_eq!(response.status, 421);
+        assert_eq!(route.unwrap().protocols_str(), "h2");
+
+        let (response, route) = router.dispatch_route(Request::new("GET", "/missing")).await;
+        assert_eq!(response.status, 404);
+        assert!(route.is_none());
+    }
+
+    #[test]
+    fn test_oversized_body_is_logged() {
+        use crate::access_log::{AccessLog, AccessLogFormat};
+        use orb_mockhttp::{HttpProtocol, RequestLimits, TestServerBuilder};
+        use std::io::{Read, Write};
+
+        let path = std::env::temp_dir().join(format!("httpbin-access-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let config = MockConfig {
+            access_log: Some(Arc::new(AccessLog::file(AccessLogFormat::Common, &path).unwrap())),
+            ..Default::default()
+        };
+        let server = TestServerBuilder::new()
+            .with_protocols(&[HttpProtocol::Http1])
+            .without_tls()
+            .with_request_limits(RequestLimits { max_body_size: Some(4), ..Default::default() })
+            .build();
+        let mut router = Router::new();
+        router.route("POST", "/post", echo_params);
+        router.install(&server, &config);
+
+        let mut stream = std::net::TcpStream::connect(server.address()).unwrap();
+        stream
+            .write_all(b"POST /post HTTP/1.1\r\nHost: x\r\nContent-Length: 10\r\nConnection: close\r\n\r\n0123456789")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413"));
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(log.contains(r#""POST /post HTTP/1.1" 413 17 "#), "{}", log);
+    }
+}
@@ -0,0 +1,450 @@
+//! Command-line flags for the server binary
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Parser, ValueEnum};
+use httpbin_handlers::client_ip::IpNetwork;
+use httpbin_handlers::TrustedProxies;
+use httpbin_mocks::{AccessLogFormat, MismatchMode};
+use orb_mockhttp::{ClientAuth, HttpProtocol, RequestLimits};
+use serde::Deserialize;
+
+use crate::config::ServerSection;
+
+/// Protocol-aware httpbin mock server
+#[derive(Debug, Parser)]
+#[command(name = "httpbin-server", version)]
+pub struct Cli {
+    /// YAML config file; flags given here override its settings
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Address to bind, e.g. 0.0.0.0 or :: for all interfaces
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub bind: IpAddr,
+
+    /// Port for TCP and, with HTTP/3, for QUIC (0 picks a free port)
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Protocols to serve, comma-separated [default: h1,h2,h3, or h1,h2 with --no-tls]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    protocols: Vec<Proto>,
+
+    /// Serve TLS (the default); overrides `tls: false` in a config file
+    #[arg(long, overrides_with = "no_tls")]
+    tls: bool,
+
+    /// Serve cleartext HTTP/1.1 and h2c only, without TLS
+    #[arg(long, overrides_with = "tls", conflicts_with_all = ["cert", "key", "cleartext_port"])]
+    pub no_tls: bool,
+
+    /// Also serve cleartext HTTP/1.1 and h2c on this port, next to TLS
+    #[arg(long, value_name = "PORT")]
+    pub cleartext_port: Option<u16>,
+
+    /// PEM certificate chain to serve instead of a generated self-signed one
+    #[arg(long, requires = "key")]
+    pub cert: Option<PathBuf>,
+
+    /// PEM private key for --cert (PKCS#8, PKCS#1 or SEC1)
+    #[arg(long, requires = "cert")]
+    pub key: Option<PathBuf>,
+
+    /// Extra name or IP for the generated certificate (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["cert", "no_tls"])]
+    pub tls_san: Vec<String>,
+
+    /// Write the CA of the generated certificate here, as PEM, for clients to trust
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["cert", "no_tls"])]
+    pub export_ca: Option<PathBuf>,
+
+    /// Write the served certificate chain here, as PEM
+    #[arg(long, value_name = "PATH", conflicts_with = "no_tls")]
+    pub export_cert: Option<PathBuf>,
+
+    /// Ask TLS clients for a certificate: "request" accepts clients without one
+    #[arg(long, value_enum, requires = "client_ca", conflicts_with = "no_tls")]
+    pub client_auth: Option<ClientAuthMode>,
+
+    /// PEM bundle of CAs that client certificates must chain to
+    #[arg(long, value_name = "PEM", requires = "client_auth")]
+    pub client_ca: Option<PathBuf>,
+
+    /// Format of the server's log output
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// Log every request, as JSON lines or in Common Log Format
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub access_log: Option<AccessLogMode>,
+
+    /// Write the access log to this file instead of standard output
+    #[arg(long, value_name = "PATH")]
+    pub access_log_file: Option<PathBuf>,
+
+    /// Serve Prometheus metrics at /metrics
+    #[arg(long, overrides_with = "no_metrics")]
+    pub metrics: bool,
+
+    /// Do not serve metrics; overrides `metrics: true` in a config file
+    #[arg(long, overrides_with = "metrics")]
+    no_metrics: bool,
+
+    /// How to reject a request over a protocol its prefix does not allow:
+    /// 421, 505, http11-required or close [default: 421]
+    #[arg(long, value_name = "MODE")]
+    pub mismatch: Option<MismatchMode>,
+
+    /// Believe forwarding headers from this proxy address or CIDR range (repeatable)
+    #[arg(long, value_name = "CIDR")]
+    pub trusted_proxy: Vec<IpNetwork>,
+
+    /// Expect a PROXY protocol v1 header on every TCP connection, from a
+    /// load balancer in front; the address in it becomes the peer address
+    #[arg(long, overrides_with = "no_proxy_protocol")]
+    pub proxy_protocol: bool,
+
+    /// Do not expect PROXY headers; overrides `proxy_protocol: true` in a config file
+    #[arg(long, overrides_with = "proxy_protocol")]
+    no_proxy_protocol: bool,
+
+    /// Reject request bodies larger than this many bytes with 413
+    #[arg(long, value_name = "BYTES")]
+    pub max_body_size: Option<usize>,
+
+    /// Limit on the total size of request headers, in bytes
+    #[arg(long, value_name = "BYTES")]
+    pub max_header_size: Option<usize>,
+
+    /// Runtime worker threads (defaults to the number of CPUs)
+    #[arg(long)]
+    pub workers: Option<usize>,
+
+    /// Seconds in-flight requests get to finish after SIGINT or SIGTERM
+    #[arg(long, value_name = "SECS", default_value_t = 10)]
+    pub shutdown_timeout: u64,
+}
+
+impl Cli {
+    /// Take settings from a config file for anything not given on the command line
+    pub fn apply_file(&mut self, file: ServerSection, matches: &ArgMatches) {
+        let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        fn fill<T>(field: &mut T, value: Option<T>, given: bool) {
+            if let (Some(value), false) = (value, given) {
+                *field = value;
+            }
+        }
+
+        fill(&mut self.bind, file.bind, given("bind"));
+        fill(&mut self.port, file.port, given("port"));
+        fill(&mut self.protocols, file.protocols, given("protocols"));
+        fill(&mut self.no_tls, file.tls.map(|tls| !tls), given("tls") || given("no_tls"));
+        self.cleartext_port = self.cleartext_port.or(file.cleartext_port);
+        self.cert = self.cert.take().or(file.cert);
+        self.key = self.key.take().or(file.key);
+        fill(&mut self.tls_san, file.tls_san, given("tls_san"));
+        self.export_ca = self.export_ca.take().or(file.export_ca);
+        self.export_cert = self.export_cert.take().or(file.export_cert);
+        self.client_auth = self.client_auth.or(file.client_auth);
+        self.client_ca = self.client_ca.take().or(file.client_ca);
+        fill(&mut self.log_format, file.log_format, given("log_format"));
+        self.access_log = self.access_log.or(file.access_log);
+        self.access_log_file = self.access_log_file.take().or(file.access_log_file);
+        fill(&mut self.metrics, file.metrics, given("metrics") || given("no_metrics"));
+        self.mismatch = self.mismatch.or(file.mismatch);
+        fill(&mut self.trusted_proxy, file.trusted_proxies, given("trusted_proxy"));
+        fill(
+            &mut self.proxy_protocol,
+            file.proxy_protocol,
+            given("proxy_protocol") || given("no_proxy_protocol"),
+        );
+        self.max_body_size = self.max_body_size.or(file.max_body_size);
+        self.max_header_size = self.max_header_size.or(file.max_header_size);
+        self.workers = self.workers.or(file.workers);
+        fill(&mut self.shutdown_timeout, file.shutdown_timeout, given("shutdown_timeout"));
+    }
+
+    /// Check combinations clap cannot express, or that come from a config file
+    pub fn validate(&self) -> Result<(), String> {
+        if self.no_tls && self.protocols().contains(&Proto::H3) {
+            return Err("HTTP/3 requires TLS; drop h3 from --protocols or --no-tls".to_string());
+        }
+        if self.no_tls && (self.cert.is_some() || self.cleartext_port.is_some() || self.client_auth.is_some()) {
+            return Err("--no-tls cannot be combined with certificates, client auth or --cleartext-port".to_string());
+        }
+        if self.cert.is_some() != self.key.is_some() {
+            return Err("--cert and --key must be given together".to_string());
+        }
+        if self.client_auth.is_some() != self.client_ca.is_some() {
+            return Err("--client-auth and --client-ca must be given together".to_string());
+        }
+        if self.access_log_file.is_some() && self.access_log.is_none() {
+            return Err("--access-log-file needs --access-log json or common".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.bind, self.port)
+    }
+
+    /// The protocols to serve, with the default filled in
+    pub fn protocols(&self) -> Vec<Proto> {
+        match (self.protocols.is_empty(), self.no_tls) {
+            (false, _) => self.protocols.clone(),
+            (true, false) => vec![Proto::H1, Proto::H2, Proto::H3],
+            (true, true) => vec![Proto::H1, Proto::H2],
+        }
+    }
+
+    /// Names for a generated certificate: `--tls-san`, plus a specific bind address
+    pub fn tls_names(&self) -> Vec<String> {
+        let mut names = self.tls_san.clone();
+        if !self.bind.is_unspecified() && !self.bind.is_loopback() {
+            names.push(self.bind.to_string());
+        }
+        names
+    }
+
+    pub fn cleartext_addr(&self) -> Option<SocketAddr> {
+        self.cleartext_port.map(|port| SocketAddr::new(self.bind, port))
+    }
+
+    pub fn http_protocols(&self) -> Vec<HttpProtocol> {
+        self.protocols().iter().map(|p| p.http_protocol()).collect()
+    }
+
+    pub fn request_limits(&self) -> RequestLimits {
+        RequestLimits {
+            max_body_size: self.max_body_size,
+            max_header_size: self.max_header_size,
+        }
+    }
+
+    pub fn trusted_proxies(&self) -> TrustedProxies {
+        TrustedProxies::new(self.trusted_proxy.clone())
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout)
+    }
+}
+
+/// A protocol name accepted by `--protocols`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Proto {
+    /// HTTP/1.1
+    H1,
+    /// HTTP/2 (over TLS, or h2c in cleartext)
+    H2,
+    /// HTTP/3 (over QUIC)
+    H3,
+}
+
+impl Proto {
+    pub fn http_protocol(&self) -> HttpProtocol {
+        match self {
+            Proto::H1 => HttpProtocol::Http1,
+            Proto::H2 => HttpProtocol::Http2,
+            Proto::H3 => HttpProtocol::Http3,
+        }
+    }
+
+    pub fn description(&self, tls: bool) -> &'static str {
+        match self {
+            Proto::H1 if tls => "HTTP/1.1 over TLS",
+            Proto::H1 => "HTTP/1.1",
+            Proto::H2 if tls => "HTTP/2 over TLS",
+            Proto::H2 => "HTTP/2 cleartext (h2c)",
+            Proto::H3 => "HTTP/3 over QUIC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientAuthMode {
+    /// Ask for a certificate, but accept clients without one
+    Request,
+    /// Reject clients without a valid certificate
+    Require,
+}
+
+impl From<ClientAuthMode> for ClientAuth {
+    fn from(mode: ClientAuthMode) -> Self {
+        match mode {
+            ClientAuthMode::Request => ClientAuth::Request,
+            ClientAuthMode::Require => ClientAuth::Require,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLogMode {
+    /// One JSON object per request
+    Json,
+    /// Common Log Format, followed by duration (ms) and protocol constraint
+    #[value(alias = "clf")]
+    #[serde(alias = "clf")]
+    Common,
+}
+
+impl From<AccessLogMode> for AccessLogFormat {
+    fn from(mode: AccessLogMode) -> Self {
+        match mode {
+            AccessLogMode::Json => AccessLogFormat::Json,
+            AccessLogMode::Common => AccessLogFormat::Common,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileConfig;
+    use clap::{CommandFactory, FromArgMatches};
+    use std::path::Path;
+
+    #[test]
+    fn test_command() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        let cli = Cli::parse_from(["httpbin-server"]);
+        assert_eq!(cli.addr(), "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(cli.protocols(), [Proto::H1, Proto::H2, Proto::H3]);
+        assert_eq!(cli.shutdown_timeout(), Duration::from_secs(10));
+
+        let cli = Cli::parse_from([
+            "httpbin-server",
+            "--bind",
+            "::",
+            "--port",
+            "9000",
+            "--protocols",
+            "h1,h3",
+            "--max-body-size",
+            "1024",
+            "--shutdown-timeout",
+            "0",
+        ]);
+        assert_eq!(cli.addr(), "[::]:9000".parse().unwrap());
+        assert_eq!(cli.protocols(), [Proto::H1, Proto::H3]);
+        assert_eq!(cli.request_limits().max_body_size, Some(1024));
+        assert_eq!(cli.shutdown_timeout(), Duration::ZERO);
+        assert!(cli.validate().is_ok());
+
+        let cli = Cli::parse_from(["httpbin-server", "--no-tls"]);
+        assert_eq!(cli.protocols(), [Proto::H1, Proto::H2]);
+        assert!(cli.validate().is_ok());
+        let cli = Cli::parse_from(["httpbin-server", "--no-tls", "--protocols", "h2,h3"]);
+        assert!(cli.validate().is_err());
+        assert!(Cli::try_parse_from(["httpbin-server", "--no-tls", "--cleartext-port", "8081"]).is_err());
+        assert!(Cli::try_parse_from(["httpbin-server", "--cert", "cert.pem"]).is_err());
+
+        let cli = Cli::parse_from(["httpbin-server", "--bind", "10.0.0.5", "--tls-san", "mock.test"]);
+        assert_eq!(cli.tls_names(), ["mock.test", "10.0.0.5"]);
+        let args = ["httpbin-server", "--cert", "c.pem", "--key", "k.pem", "--export-ca", "ca.pem"];
+        assert!(Cli::try_parse_from(args).is_err());
+
+        let cli = Cli::parse_from(["httpbin-server", "--client-auth", "require", "--client-ca", "ca.pem"]);
+        assert_eq!(cli.client_auth, Some(ClientAuthMode::Require));
+        assert!(Cli::try_parse_from(["httpbin-server", "--client-auth", "request"]).is_err());
+
+        let cli = Cli::parse_from(["httpbin-server", "--access-log", "clf", "--access-log-file", "access.log"]);
+        assert_eq!(cli.access_log, Some(AccessLogMode::Common));
+        assert!(cli.validate().is_ok());
+        let cli = Cli::parse_from(["httpbin-server", "--access-log-file", "access.log"]);
+        assert!(cli.validate().is_err());
+
+        let cli = Cli::parse_from(["httpbin-server", "--trusted-proxy", "10.0.0.0/8", "--trusted-proxy", "::1"]);
+        assert!(cli.trusted_proxies().contains("10.1.2.3".parse().unwrap()));
+        assert!(cli.trusted_proxies().contains("::1".parse().unwrap()));
+        assert!(Cli::try_parse_from(["httpbin-server", "--trusted-proxy", "10.0.0.0/33"]).is_err());
+
+        let cli = Cli::parse_from(["httpbin-server", "--mismatch", "http11-required"]);
+        assert_eq!(cli.mismatch, Some(MismatchMode::Http11Required));
+        assert!(!cli.proxy_protocol);
+        assert!(Cli::parse_from(["httpbin-server", "--proxy-protocol"]).proxy_protocol);
+        assert!(Cli::try_parse_from(["httpbin-server", "--mismatch", "404"]).is_err());
+    }
+
+    #[test]
+    fn test_apply_file() {
+        let file = || ServerSection {
+            port: Some(9000),
+            protocols: Some(vec![Proto::H1]),
+            max_body_size: Some(10),
+            shutdown_timeout: Some(3),
+            metrics: Some(true),
+            trusted_proxies: Some(vec!["192.0.2.1".parse().unwrap()]),
+            proxy_protocol: Some(true),
+            ..Default::default()
+        };
+
+        // The file fills in what the command line leaves out
+        let matches = Cli::command().get_matches_from(["httpbin-server", "--max-body-size", "20"]);
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        cli.apply_file(file(), &matches);
+        assert_eq!(cli.port, 9000);
+        assert_eq!(cli.protocols(), [Proto::H1]);
+        assert_eq!(cli.request_limits().max_body_size, Some(20));
+        assert_eq!(cli.shutdown_timeout(), Duration::from_secs(3));
+        assert!(cli.metrics);
+        assert!(cli.trusted_proxies().contains("192.0.2.1".parse().unwrap()));
+        assert!(cli.proxy_protocol);
+
+        // Flags win, even when they repeat a default
+        let matches = Cli::command().get_matches_from(["httpbin-server", "--port", "8080"]);
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        cli.apply_file(file(), &matches);
+        assert_eq!(cli.port, 8080);
+
+        let mut cli = Cli::parse_from(["httpbin-server", "--protocols", "h2,h3"]);
+        let matches = Cli::command().get_matches_from(["httpbin-server", "--protocols", "h2,h3"]);
+        cli.apply_file(ServerSection { tls: Some(false), ..Default::default() }, &matches);
+        assert!(cli.validate().is_err());
+
+        // Boolean settings can be overridden either way
+        let args = ["httpbin-server", "--tls", "--no-metrics", "--no-proxy-protocol"];
+        let matches = Cli::command().get_matches_from(args);
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        cli.apply_file(ServerSection { tls: Some(false), ..file() }, &matches);
+        assert!(!cli.no_tls);
+        assert!(!cli.metrics);
+        assert!(!cli.proxy_protocol);
+        let matches = Cli::command().get_matches_from(["httpbin-server", "--no-tls", "--tls"]);
+        let cli = Cli::from_arg_matches(&matches).unwrap();
+        assert!(!cli.no_tls);
+    }
+
+    #[test]
+    fn test_example_config() {
+        let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../config.example.yaml"));
+        let file = FileConfig::load(path).unwrap();
+
+        let matches = Cli::command().get_matches_from(["httpbin-server", "--no-tls"]);
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        cli.apply_file(file.server, &matches);
+        assert!(cli.no_tls);
+        assert_eq!(cli.protocols(), [Proto::H1, Proto::H2]);
+        assert!(cli.validate().is_ok());
+    }
+}
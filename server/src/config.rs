@@ -0,0 +1,315 @@
+//! YAML configuration file for the server binary
+//!
+//! Every setting is optional. Flags given on the command line win over the
+//! file, and the file wins over the built-in defaults. Relative paths are
+//! resolved against the directory of the file.
+
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use httpbin_handlers::client_ip::IpNetwork;
+use httpbin_handlers::EndpointSettings;
+use httpbin_mocks::{CorsConfig, EndpointGroup, MismatchMode, Mount};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+
+use crate::cli::{AccessLogMode, ClientAuthMode, LogFormat, Proto};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    pub server: ServerSection,
+    pub endpoints: EndpointSection,
+    pub cors: CorsSection,
+}
+
+/// Listener, TLS and runtime settings, named after the command-line flags
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSection {
+    pub bind: Option<IpAddr>,
+    pub port: Option<u16>,
+    pub protocols: Option<Vec<Proto>>,
+    /// `false` is the same as `--no-tls`
+    pub tls: Option<bool>,
+    pub cleartext_port: Option<u16>,
+    pub cert: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+    pub tls_san: Option<Vec<String>>,
+    pub export_ca: Option<PathBuf>,
+    pub export_cert: Option<PathBuf>,
+    pub client_auth: Option<ClientAuthMode>,
+    pub client_ca: Option<PathBuf>,
+    pub log_format: Option<LogFormat>,
+    pub access_log: Option<AccessLogMode>,
+    pub access_log_file: Option<PathBuf>,
+    /// `true` is the same as `--metrics`
+    pub metrics: Option<bool>,
+    /// 421, 505, http11-required or close
+    #[serde(deserialize_with = "mismatch")]
+    pub mismatch: Option<MismatchMode>,
+    /// Proxy addresses or CIDR ranges whose forwarding headers are believed
+    #[serde(deserialize_with = "networks")]
+    pub trusted_proxies: Option<Vec<IpNetwork>>,
+    /// `true` is the same as `--proxy-protocol`
+    pub proxy_protocol: Option<bool>,
+    pub max_body_size: Option<usize>,
+    pub max_header_size: Option<usize>,
+    pub workers: Option<usize>,
+    pub shutdown_timeout: Option<u64>,
+}
+
+/// Endpoint limits and reported values, and which endpoints are served where
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndpointSection {
+    /// Scheme and authority reported in `url` fields
+    pub base_url: Option<String>,
+    /// Realm of Basic and Digest auth challenges
+    pub realm: Option<String>,
+    pub max_bytes: Option<usize>,
+    /// Seconds
+    pub max_delay: Option<u64>,
+    pub max_stream_lines: Option<usize>,
+    pub max_links: Option<usize>,
+    /// Prefixes to mount the endpoints under, e.g. "/", "h1" or "h2h3"
+    #[serde(deserialize_with = "mounts")]
+    pub mounts: Option<Vec<Mount>>,
+    /// Endpoint groups to register, e.g. "http-methods" or "dynamic-data"
+    #[serde(deserialize_with = "groups")]
+    pub groups: Option<Vec<EndpointGroup>>,
+}
+
+/// CORS headers added to every response
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsSection {
+    /// `false` sends no CORS headers at all
+    pub enabled: Option<bool>,
+    /// Origins to allow; empty or unset allows (and echoes) any origin
+    pub origins: Option<Vec<String>>,
+    /// Send `Access-Control-Allow-Credentials: true`
+    pub credentials: Option<bool>,
+    /// Seconds preflight responses may be cached
+    pub max_age: Option<u64>,
+}
+
+impl FileConfig {
+    /// Read and check a config file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config {}: {}", path.display(), e))?;
+        let mut config: FileConfig = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        config
+            .endpoints
+            .check()
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let server = &mut config.server;
+        for file in [
+            &mut server.cert,
+            &mut server.key,
+            &mut server.export_ca,
+            &mut server.export_cert,
+            &mut server.client_ca,
+            &mut server.access_log_file,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *file = dir.join(&*file);
+        }
+        Ok(config)
+    }
+}
+
+impl EndpointSection {
+    fn check(&mut self) -> Result<(), String> {
+        if let Some(url) = &mut self.base_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("endpoints.base_url must start with http:// or https://, got '{}'", url));
+            }
+            url.truncate(url.trim_end_matches('/').len());
+        }
+        if let Some(realm) = &self.realm {
+            if realm.contains(['"', '\\']) {
+                return Err("endpoints.realm cannot contain quotes or backslashes".to_string());
+            }
+        }
+        if self.mounts.as_ref().is_some_and(Vec::is_empty) {
+            return Err("endpoints.mounts cannot be empty".to_string());
+        }
+        if self.groups.as_ref().is_some_and(Vec::is_empty) {
+            return Err("endpoints.groups cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Handler settings, with defaults for anything not configured
+    pub fn settings(&self) -> EndpointSettings {
+        let defaults = EndpointSettings::default();
+        EndpointSettings {
+            base_url: self.base_url.clone().unwrap_or(defaults.base_url),
+            realm: self.realm.clone().unwrap_or(defaults.realm),
+            max_bytes: self.max_bytes.unwrap_or(defaults.max_bytes),
+            max_delay: self.max_delay.unwrap_or(defaults.max_delay),
+            max_stream_lines: self.max_stream_lines.unwrap_or(defaults.max_stream_lines),
+            max_links: self.max_links.unwrap_or(defaults.max_links),
+        }
+    }
+
+    /// Configured mounts, or `/`, `/h1`, `/h2` and `/h3`
+    pub fn mounts(&self) -> Vec<Mount> {
+        self.mounts.clone().unwrap_or_else(Mount::defaults)
+    }
+
+    /// Configured endpoint groups, or all of them
+    pub fn groups(&self) -> Vec<EndpointGroup> {
+        self.groups.clone().unwrap_or_else(|| EndpointGroup::ALL.to_vec())
+    }
+}
+
+impl CorsSection {
+    /// CORS settings, with httpbin's defaults for anything not configured
+    pub fn settings(&self) -> CorsConfig {
+        let defaults = CorsConfig::default();
+        CorsConfig {
+            enabled: self.enabled.unwrap_or(defaults.enabled),
+            allowed_origins: self.origins.clone().unwrap_or(defaults.allowed_origins),
+            allow_credentials: self.credentials.unwrap_or(defaults.allow_credentials),
+            max_age: self.max_age.unwrap_or(defaults.max_age),
+        }
+    }
+}
+
+fn mounts<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<Mount>>, D::Error> {
+    let names = Option::<Vec<String>>::deserialize(deserializer)?;
+    names
+        .map(|names| {
+            names
+                .iter()
+                .map(|name| {
+                    Mount::parse(name).ok_or_else(|| {
+                        D::Error::custom(format!(
+                            "unknown mount '{}' (expected \"/\" or protocols such as h1, h2h3)",
+                            name
+                        ))
+                    })
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+/// A mismatch mode, written as a status code (`421`) or a name (`close`)
+fn mismatch<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<MismatchMode>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Mode {
+        Status(u16),
+        Name(String),
+    }
+
+    let mode = Option::<Mode>::deserialize(deserializer)?;
+    mode.map(|mode| match mode {
+        Mode::Status(status) => status.to_string().parse(),
+        Mode::Name(name) => name.parse(),
+    })
+    .transpose()
+    .map_err(D::Error::custom)
+}
+
+fn networks<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<IpNetwork>>, D::Error> {
+    let entries = Option::<Vec<String>>::deserialize(deserializer)?;
+    entries
+        .map(|entries| entries.iter().map(|entry| entry.parse().map_err(D::Error::custom)).collect())
+        .transpose()
+}
+
+fn groups<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<EndpointGroup>>, D::Error> {
+    let names = Option::<Vec<String>>::deserialize(deserializer)?;
+    names
+        .map(|names| names.iter().map(|name| name.parse().map_err(D::Error::custom)).collect())
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: FileConfig = serde_yaml::from_str(
+            r#"
+server:
+  bind: "::"
+  port: 9000
+  protocols: [h1, h2]
+  tls: false
+  shutdown_timeout: 3
+  access_log: common
+  metrics: true
+  trusted_proxies: [10.0.0.0/8, "::1"]
+  proxy_protocol: true
+  mismatch: 505
+endpoints:
+  base_url: http://mock.test:9000/
+  realm: Mock Realm
+  max_delay: 2
+  mounts: ["/", h2h3]
+  groups: [http-methods, dynamic-data]
+cors:
+  origins: [https://app.example]
+  credentials: false
+  max_age: 60
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.server.port, Some(9000));
+        assert_eq!(config.server.protocols, Some(vec![Proto::H1, Proto::H2]));
+        assert_eq!(config.server.tls, Some(false));
+        assert_eq!(config.server.access_log, Some(AccessLogMode::Common));
+        assert_eq!(config.server.metrics, Some(true));
+        assert_eq!(config.server.mismatch, Some(MismatchMode::VersionNotSupported));
+        assert_eq!(config.server.trusted_proxies.map(|p| p.len()), Some(2));
+        assert_eq!(config.server.proxy_protocol, Some(true));
+
+        let mut endpoints = config.endpoints;
+        endpoints.check().unwrap();
+        let settings = endpoints.settings();
+        assert_eq!(settings.base_url, "http://mock.test:9000");
+        assert_eq!(settings.realm, "Mock Realm");
+        assert_eq!(settings.max_delay, 2);
+        assert_eq!(settings.max_bytes, 102400);
+        assert_eq!(endpoints.mounts()[1].prefix, "/h2h3");
+        assert_eq!(endpoints.groups(), [EndpointGroup::HttpMethods, EndpointGroup::DynamicData]);
+
+        let cors = config.cors.settings();
+        assert!(cors.enabled);
+        assert_eq!(cors.allowed_origins, ["https://app.example"]);
+        assert!(!cors.allow_credentials);
+        assert_eq!(cors.max_age, 60);
+        assert_eq!(FileConfig::default().cors.settings(), CorsConfig::default());
+    }
+
+    #[test]
+    fn test_reject_invalid_config() {
+        let parse = |yaml: &str| serde_yaml::from_str::<FileConfig>(yaml);
+
+        assert!(parse("server:\n  prot: 8080\n").is_err());
+        assert!(parse("server:\n  mismatch: 404\n").is_err());
+        assert_eq!(parse("server:\n  mismatch: close\n").unwrap().server.mismatch, Some(MismatchMode::CloseConnection));
+        assert!(parse("server:\n  trusted_proxies: [10.0.0.0/40]\n").is_err());
+        assert!(parse("endpoints:\n  mounts: [http2]\n").is_err());
+        assert!(parse("endpoints:\n  groups: [streaming]\n").is_err());
+        assert!(parse("cors:\n  origin: [https://app.example]\n").is_err());
+
+        let mut endpoints = parse("endpoints:\n  base_url: httpbin.org\n").unwrap().endpoints;
+        assert!(endpoints.check().is_err());
+        let mut endpoints = parse("endpoints:\n  groups: []\n").unwrap().endpoints;
+        assert!(endpoints.check().is_err());
+    }
+}
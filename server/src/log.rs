@@ -0,0 +1,49 @@
+//! Server log output, as text or JSON lines
+
+use serde_json::{json, Map, Value};
+
+use crate::cli::LogFormat;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Log {
+    format: LogFormat,
+}
+
+impl Log {
+    pub fn new(format: LogFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.format == LogFormat::Text
+    }
+
+    /// Human-readable output; only shown in text mode
+    pub fn text(&self, line: impl AsRef<str>) {
+        if self.is_text() {
+            println!("{}", line.as_ref());
+        }
+    }
+
+    /// A structured event; only shown in JSON mode, where text lines would
+    /// break line-oriented parsers
+    pub fn event(&self, event: &str, fields: Value) {
+        if self.is_text() {
+            return;
+        }
+        let mut object = Map::new();
+        object.insert("event".to_string(), json!(event));
+        if let Value::Object(fields) = fields {
+            object.extend(fields);
+        }
+        println!("{}", Value::Object(object));
+    }
+
+    /// An error, in either format
+    pub fn error(&self, message: impl AsRef<str>) {
+        match self.format {
+            LogFormat::Text => eprintln!("{}", message.as_ref()),
+            LogFormat::Json => eprintln!("{}", json!({ "event": "error", "message": message.as_ref() })),
+        }
+    }
+}
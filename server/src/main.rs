@@ -1,128 +1,253 @@
 //! httpbin-proto-mock server
 //! Protocol-aware HTTP testing server
 
-use orb_mockhttp::{TestServerBuilder, HttpProtocol};
-use std::io::{Read, Write};
-use std::net::{TcpListener, TcpStream};
+mod cli;
+mod config;
+mod log;
 
-const DEFAULT_PORT: u16 = 8080;
+use clap::{CommandFactory, FromArgMatches};
+use orb_mockhttp::{TestServerBuilder, TlsConfig};
+use httpbin_mocks::{AccessLog, EndpointGroup, Metrics, MockConfig, Router};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+use cli::Cli;
+use config::FileConfig;
+use log::Log;
 
 fn main() {
-    let fixed_port: u16 = std::env::args()
-        .nth(1)
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(DEFAULT_PORT);
-
-    println!("=== httpbin-proto-mock Server ===");
-    println!();
-    println!("Building test server with HTTP/1.1, HTTP/2, and HTTP/3 support...");
-
-    let server = TestServerBuilder::new()
-        .with_tls()
-        .with_protocols(&[
-            HttpProtocol::Http1,
-            HttpProtocol::Http2,
-            HttpProtocol::Http3,
-        ])
-        .build();
-
-    println!("Registering protocol-agnostic endpoints (52)...");
-    httpbin_mocks::register_any_protocol_mocks(&server);
-
-    println!("Registering HTTP/1.1-only endpoints (52)...");
-    httpbin_mocks::register_h1_mocks(&server);
-
-    println!("Registering HTTP/2-only endpoints (52)...");
-    httpbin_mocks::register_h2_mocks(&server);
-
-    println!("Registering HTTP/3-only endpoints (52)...");
-    httpbin_mocks::register_h3_mocks(&server);
-
-    let internal_port = server.port();
-
-    println!();
-    println!("All 208 endpoints registered!");
-    println!();
-    println!("Server ready at: https://127.0.0.1:{}/", fixed_port);
-    println!("  - HTTP/1.1 over TLS");
-    println!("  - HTTP/2 over TLS");
-    println!("  - HTTP/3 over QUIC");
-    println!();
-    println!("Example endpoints:");
-    println!("  https://127.0.0.1:{}/status/200", fixed_port);
-    println!("  https://127.0.0.1:{}/get", fixed_port);
-    println!("  https://127.0.0.1:{}/uuid", fixed_port);
-    println!();
-    println!("Press Ctrl+C to stop");
-
-    // Listen on the fixed port and proxy to the internal random port
-    let listener = TcpListener::bind(format!("127.0.0.1:{}", fixed_port))
-        .unwrap_or_else(|e| {
-            eprintln!("Failed to bind to port {}: {}", fixed_port, e);
-            eprintln!("Try a different port: ./httpbin-server <port>");
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    let file = match &cli.config {
+        Some(path) => FileConfig::load(path).unwrap_or_else(|e| {
+            Log::new(cli.log_format).error(e);
+            std::process::exit(2);
+        }),
+        None => FileConfig::default(),
+    };
+    cli.apply_file(file.server, &matches);
+    let endpoints = file.endpoints;
+    let cors = file.cors;
+    let log = Log::new(cli.log_format);
+
+    if let Err(e) = cli.validate() {
+        log.error(format!("error: {}", e));
+        std::process::exit(2);
+    }
+
+    let worker_threads = cli.workers.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+
+    log.text("=== httpbin-proto-mock Server ===");
+    log.text("");
+
+    // No request log: it would keep every request for the life of the server
+    let mut builder = TestServerBuilder::new()
+        .with_protocols(&cli.http_protocols())
+        // TCP and QUIC both listen on the fixed port directly
+        .with_addr(cli.addr())
+        .with_worker_threads(worker_threads)
+        .with_request_limits(cli.request_limits());
+
+    match tls_config(&cli) {
+        Ok(Some(tls)) => builder = builder.with_tls_config(tls),
+        Ok(None) => builder = builder.without_tls(),
+        Err(e) => {
+            log.error(e);
             std::process::exit(1);
-        });
-
-    for incoming in listener.incoming() {
-        match incoming {
-            Ok(client) => {
-                let target_port = internal_port;
-                std::thread::spawn(move || {
-                    proxy_connection(client, target_port);
-                });
-            }
-            Err(e) => eprintln!("Connection error: {}", e),
         }
     }
-}
+    if let Some(addr) = cli.cleartext_addr() {
+        builder = builder.with_cleartext_addr(addr);
+    }
+    if cli.proxy_protocol {
+        builder = builder.with_proxy_protocol();
+    }
 
-fn proxy_connection(mut client: TcpStream, target_port: u16) {
-    let mut target = match TcpStream::connect(format!("127.0.0.1:{}", target_port)) {
-        Ok(t) => t,
-        Err(_) => return,
-    };
+    let server = builder.try_build().unwrap_or_else(|e| {
+        log.error(format!("Failed to start server on {}: {}", cli.addr(), e));
+        log.error("Try a different port: httpbin-server --port <port>");
+        std::process::exit(1);
+    });
 
-    let mut client_clone = match client.try_clone() {
-        Ok(c) => c,
-        Err(_) => return,
-    };
-    let mut target_clone = match target.try_clone() {
-        Ok(t) => t,
-        Err(_) => return,
+    export_pem(&log, cli.export_ca.as_deref(), server.ca_pem());
+    export_pem(&log, cli.export_cert.as_deref(), server.chain_pem());
+
+    let metrics = cli.metrics.then(|| Arc::new(Metrics::new(server.active_connections())));
+    let config = MockConfig {
+        endpoints: endpoints.settings(),
+        trusted_proxies: cli.trusted_proxies(),
+        cors: cors.settings(),
+        mismatch: cli.mismatch.unwrap_or_default(),
+        access_log: access_log(&log, &cli),
+        metrics: metrics.clone(),
     };
+    let mut router = Router::new();
+
+    let mounts = endpoints.mounts();
+    let groups = endpoints.groups();
+    for mount in &mounts {
+        match mount.protocols {
+            Some(protocols) => log.text(format!("Registering {}/ endpoints ({} only)...", mount.prefix, protocols)),
+            None => log.text("Registering protocol-agnostic endpoints..."),
+        }
+    }
+    if groups.len() < EndpointGroup::ALL.len() {
+        let names: Vec<&str> = groups.iter().map(|g| g.as_str()).collect();
+        log.text(format!("  endpoint groups: {}", names.join(", ")));
+    }
+    httpbin_mocks::register_groups(&mut router, &mounts, &groups);
+    if let Some(metrics) = &metrics {
+        log.text("Registering /metrics...");
+        metrics.register(&mut router);
+    }
 
-    // Client -> Target
-    let t1 = std::thread::spawn(move || {
-        let mut buf = [0u8; 8192];
-        loop {
-            match client.read(&mut buf) {
-                Ok(0) | Err(_) => break,
-                Ok(n) => {
-                    if target.write_all(&buf[..n]).is_err() {
-                        break;
-                    }
-                }
+    let route_count = router.len();
+    router.install(&server, &config);
+
+    let protocols: Vec<&str> = cli.protocols().iter().map(|p| p.description(server.is_tls())).collect();
+    log.event(
+        "listening",
+        json!({
+            "addr": cli.addr().to_string(),
+            "url": server.url("/"),
+            "cleartext_url": server.cleartext_url("/"),
+            "tls": server.is_tls(),
+            "protocols": protocols,
+            "routes": route_count,
+            "metrics_url": metrics.as_ref().map(|_| server.url("/metrics")),
+        }),
+    );
+
+    log.text("");
+    log.text(format!("All {} routes registered!", route_count));
+    log.text("");
+    log.text(format!("Server ready at: {}", server.url("/")));
+    for protocol in &protocols {
+        log.text(format!("  - {}", protocol));
+    }
+    if let Some(url) = server.cleartext_url("/") {
+        let h2c = if cli.protocols().contains(&cli::Proto::H2) { " and h2c" } else { "" };
+        log.text(format!("Cleartext HTTP/1.1{} at: {}", h2c, url));
+    }
+    log.text("");
+    log.text("Example endpoints:");
+    log.text(format!("  {}", server.url("/status/200")));
+    log.text(format!("  {}", server.url("/get")));
+    log.text(format!("  {}", server.url("/uuid")));
+    log.text("");
+    log.text("Press Ctrl+C to stop");
+
+    // The server runs on its own thread; this one waits for a signal to stop it
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to create signal runtime");
+    runtime.block_on(async {
+        let signal = shutdown_signal().await;
+        let timeout = cli.shutdown_timeout();
+        log.text("");
+        log.text(format!(
+            "Received {}, finishing in-flight requests (up to {}s, signal again to stop now)...",
+            signal,
+            timeout.as_secs()
+        ));
+        log.event("shutting_down", json!({ "signal": signal, "timeout_secs": timeout.as_secs() }));
+
+        let drain = tokio::task::spawn_blocking(move || server.shutdown_gracefully(timeout));
+        tokio::select! {
+            _ = drain => {
+                log.text("Server stopped");
+                log.event("stopped", json!({}));
+            }
+            signal = shutdown_signal() => {
+                log.text(format!("Received {} again, stopping now", signal));
+                log.event("stopped", json!({ "forced": true }));
+                std::process::exit(1);
             }
         }
-        let _ = target.shutdown(std::net::Shutdown::Write);
     });
+}
 
-    // Target -> Client
-    let t2 = std::thread::spawn(move || {
-        let mut buf = [0u8; 8192];
-        loop {
-            match target_clone.read(&mut buf) {
-                Ok(0) | Err(_) => break,
-                Ok(n) => {
-                    if client_clone.write_all(&buf[..n]).is_err() {
-                        break;
-                    }
-                }
-            }
+/// Wait for SIGINT (Ctrl+C) or SIGTERM, and return its name
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => "SIGINT",
+            _ = terminate.recv() => "SIGTERM",
         }
-        let _ = client_clone.shutdown(std::net::Shutdown::Write);
-    });
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl+C"
+    }
+}
 
-    let _ = t1.join();
-    let _ = t2.join();
+/// The TLS setup the flags ask for, or None for cleartext only
+fn tls_config(cli: &Cli) -> Result<Option<TlsConfig>, String> {
+    if cli.no_tls {
+        return Ok(None);
+    }
+
+    let tls = match (&cli.cert, &cli.key) {
+        (Some(cert), Some(key)) => {
+            TlsConfig::from_files(cert.clone(), key.clone()).map_err(|e| {
+                format!(
+                    "Failed to load certificate {} / key {}: {}",
+                    cert.display(),
+                    key.display(),
+                    e
+                )
+            })?
+        }
+        _ => {
+            let names = cli.tls_names();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            TlsConfig::generate_for(&names)
+        }
+    };
+
+    match (cli.client_auth, &cli.client_ca) {
+        (Some(mode), Some(bundle)) => tls
+            .with_client_auth(mode.into(), bundle.clone())
+            .map(Some)
+            .map_err(|e| format!("Failed to load client CA bundle {}: {}", bundle.display(), e)),
+        _ => Ok(Some(tls)),
+    }
+}
+
+/// The access log `--access-log` and `--access-log-file` ask for
+fn access_log(log: &Log, cli: &Cli) -> Option<Arc<AccessLog>> {
+    let format = cli.access_log?.into();
+    let access_log = match &cli.access_log_file {
+        Some(path) => AccessLog::file(format, path).unwrap_or_else(|e| {
+            log.error(format!("Failed to open access log {}: {}", path.display(), e));
+            std::process::exit(1);
+        }),
+        None => AccessLog::stdout(format),
+    };
+    Some(Arc::new(access_log))
+}
+
+/// Write a PEM file requested on the command line
+fn export_pem(log: &Log, path: Option<&Path>, pem: Option<String>) {
+    let (Some(path), Some(pem)) = (path, pem) else {
+        return;
+    };
+    if let Err(e) = std::fs::write(path, pem) {
+        log.error(format!("Failed to write {}: {}", path.display(), e));
+        std::process::exit(1);
+    }
+    log.text(format!("Wrote {}", path.display()));
+    log.event("exported", json!({ "path": path.display().to_string() }));
 }